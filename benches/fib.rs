@@ -0,0 +1,84 @@
+//! Compare the speed of the tour's implementations. Run with
+//! `cargo bench`. This is a simple timing loop rather than a
+//! full benchmark framework, to keep the crate free of
+//! dependencies: each implementation is run for at least
+//! `MIN_TIME` and the mean time per call is reported.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use fib_tour::*;
+
+const MIN_TIME: Duration = Duration::from_millis(100);
+
+const INDICES: [usize; 3] = [10, 30, 47];
+
+type FibFn = fn(usize) -> Option<u32>;
+
+/// Mean nanoseconds per call of `f(n)`.
+fn time_per_call(f: FibFn, n: usize) -> f64 {
+    let mut iters = 1u32;
+    loop {
+        let start = Instant::now();
+        for _ in 0..iters {
+            black_box(f(black_box(n)));
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= MIN_TIME || iters == u32::MAX {
+            return elapsed.as_secs_f64() * 1e9 / f64::from(iters);
+        }
+        iters = iters.saturating_mul(2);
+    }
+}
+
+/// Mean nanoseconds per call of `f()`.
+fn time_batch<T>(f: impl Fn() -> T) -> f64 {
+    let mut iters = 1u32;
+    loop {
+        let start = Instant::now();
+        for _ in 0..iters {
+            black_box(f());
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= MIN_TIME || iters == u32::MAX {
+            return elapsed.as_secs_f64() * 1e9 / f64::from(iters);
+        }
+        iters = iters.saturating_mul(2);
+    }
+}
+
+fn main() {
+    for n in INDICES {
+        println!("F({})", n);
+        for &method in FibMethod::ALL {
+            let ns = time_per_call(method.function(), n);
+            println!("  {:>12} {:>10.2} ns", method.name(), ns);
+        }
+    }
+
+    // With a literal index and no black_box() the lookup is
+    // folded away, so the first line times an empty loop.
+    println!("F(47), literal index");
+    println!(
+        "  {:>12} {:>10.2} ns",
+        "lookup",
+        time_batch(|| fib_lookup(47))
+    );
+    println!(
+        "  {:>12} {:>10.2} ns",
+        "black_box",
+        time_batch(|| fib_black_box(47))
+    );
+
+    let indices: Vec<usize> = (0..1024).map(|i| (i * 29) % 48).collect();
+    println!("batch of {}", indices.len());
+    let naive = || {
+        indices
+            .iter()
+            .map(|&i| fib_array(black_box(i)))
+            .collect::<Vec<_>>()
+    };
+    println!("  {:>12} {:>10.2} ns", "map", time_batch(naive));
+    let batch = || fib_batch(black_box(&indices));
+    println!("  {:>12} {:>10.2} ns", "batch", time_batch(batch));
+}
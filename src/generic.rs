@@ -0,0 +1,196 @@
+//! Fibonacci Numbers over any integer type, for when `u32`
+//! isn't wide enough.
+
+/// Integer types that Fibonacci Numbers can be computed in.
+/// This is just enough of an interface to run the
+/// checked-add recurrence, so other numeric types can join
+/// in by implementing it. Only `Clone` is required, and
+/// values are added by reference, so heap-backed big integers
+/// work as well as the primitives. A type that never
+/// overflows works with `fib()` and `fib_fold_generic()`, but
+/// not with `fib_overflow_index()` or `representable_count()`.
+pub trait FibInt: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// `self` + `rhs`, or `None` if the sum does not fit.
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_fib_int {
+    ($($t:ty),*) => {
+        $(
+            impl FibInt for $t {
+                fn zero() -> Self {
+                    0
+                }
+                fn one() -> Self {
+                    1
+                }
+                fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_fib_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// This is `fib_array()` with the output type as a
+/// parameter. It returns `Some` as long as the result fits
+/// in a `T`, and `None` otherwise.
+pub fn fib<T: FibInt>(n: usize) -> Option<T> {
+    if n == 0 {
+        return Some(T::zero());
+    }
+    let (mut x, mut y) = (T::zero(), T::one());
+    for _ in 1..n {
+        let z = x.checked_add(&y)?;
+        x = core::mem::replace(&mut y, z);
+    }
+    Some(y)
+}
+
+/// This is `fib_fold()` with the output type as a parameter.
+#[allow(clippy::manual_try_fold)]
+pub fn fib_fold_generic<T: FibInt>(n: usize) -> Option<T> {
+    (0..n)
+        .fold(Some((T::zero(), T::one())), |regs, _| {
+            regs.and_then(|(x, y)| x.checked_add(&y).map(|z| (z, x)))
+        })
+        .map(|(x, _)| x)
+}
+
+/// The first `n` for which F(n) does not fit in a `T`,
+/// found by running the recurrence until it overflows. `T`
+/// must be a type that can overflow: if its `checked_add()`
+/// never fails, this never returns.
+pub fn fib_overflow_index<T: FibInt>() -> usize {
+    // (F(n-1), F(n))
+    let (mut x, mut y) = (T::zero(), T::one());
+    let mut n = 1;
+    while let Some(z) = x.checked_add(&y) {
+        x = core::mem::replace(&mut y, z);
+        n += 1;
+    }
+    n + 1
+}
+
+/// How many Fibonacci Numbers fit in a `T`: F(0) through
+/// F(n - 1), where n is `fib_overflow_index::<T>()`. As there,
+/// `T` must be a type that can overflow.
+pub fn representable_count<T: FibInt>() -> usize {
+    fib_overflow_index::<T>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fib_lookup;
+
+    #[test]
+    fn test_fib_u32() {
+        for i in 0..=51 {
+            assert_eq!(fib_lookup(i), fib::<u32>(i));
+        }
+    }
+
+    #[test]
+    fn test_fib_wide() {
+        assert_eq!(Some(12_200_160_415_121_876_738u64), fib::<u64>(93));
+        assert!(fib::<u64>(94).is_none());
+        assert_eq!(Some(12_200_160_415_121_876_738u128), fib::<u128>(93));
+        assert_eq!(Some(55usize), fib::<usize>(10));
+        assert_eq!(Some(233u8), fib::<u8>(13));
+        assert!(fib::<u8>(14).is_none());
+    }
+
+    #[test]
+    fn test_fib_signed_types() {
+        assert_eq!(Some(12_586_269_025u64), fib::<u64>(50));
+        assert_eq!(Some(12_586_269_025i64), fib::<i64>(50));
+        assert_eq!(Some(7_540_113_804_746_346_429i64), fib::<i64>(92));
+        assert!(fib::<i64>(93).is_none());
+        assert_eq!(Some(1_836_311_903i32), fib::<i32>(46));
+        assert!(fib::<i32>(47).is_none());
+    }
+
+    #[test]
+    fn test_fib_fold_generic() {
+        for i in 0..=51 {
+            assert_eq!(crate::fib_fold(i), fib_fold_generic::<u32>(i));
+            assert_eq!(fib::<u64>(i), fib_fold_generic::<u64>(i));
+        }
+        assert_eq!(
+            Some(2_880_067_194_370_816_120u64),
+            fib_fold_generic::<u64>(90)
+        );
+        assert!(fib_fold_generic::<u64>(94).is_none());
+    }
+
+    #[test]
+    fn test_fib_overflow_index() {
+        assert_eq!(14, fib_overflow_index::<u8>());
+        assert_eq!(48, fib_overflow_index::<u32>());
+        assert_eq!(94, fib_overflow_index::<u64>());
+        assert_eq!(187, fib_overflow_index::<u128>());
+        let n = fib_overflow_index::<u32>();
+        assert!(crate::fib_array(n - 1).is_some());
+        assert!(crate::fib_array(n).is_none());
+    }
+
+    #[test]
+    fn test_representable_count() {
+        assert_eq!(48, representable_count::<u32>());
+        assert_eq!(94, representable_count::<u64>());
+        assert_eq!(187, representable_count::<u128>());
+        assert_eq!(crate::FIB_TABLE.len(), representable_count::<u32>());
+        assert_eq!(crate::FIB_TABLE_U128.len(), representable_count::<u128>());
+    }
+
+    /// A heap-backed decimal integer that never overflows, to
+    /// check that `FibInt` works for types that aren't `Copy`.
+    #[cfg(feature = "std")]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Decimal(Vec<u8>);
+
+    #[cfg(feature = "std")]
+    impl FibInt for Decimal {
+        fn zero() -> Self {
+            Decimal(vec![0])
+        }
+
+        fn one() -> Self {
+            Decimal(vec![1])
+        }
+
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            let len = self.0.len().max(rhs.0.len());
+            let mut digits = Vec::with_capacity(len + 1);
+            let mut carry = 0;
+            for i in 0..len {
+                let sum = self.0.get(i).unwrap_or(&0) + rhs.0.get(i).unwrap_or(&0) + carry;
+                digits.push(sum % 10);
+                carry = sum / 10;
+            }
+            if carry > 0 {
+                digits.push(carry);
+            }
+            Some(Decimal(digits))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_heap_backed() {
+        let to_string = |d: Decimal| d.0.iter().rev().map(|&d| char::from(b'0' + d)).collect();
+        for i in [0, 1, 2, 10, 100, 300] {
+            let expected = crate::fib_decimal_string(i);
+            assert_eq!(Some(expected.clone()), fib::<Decimal>(i).map(to_string));
+            assert_eq!(
+                Some(expected),
+                fib_fold_generic::<Decimal>(i).map(to_string)
+            );
+        }
+    }
+}
@@ -5,6 +5,15 @@
 //! number, starting with `fibonacci_number(0)` = 0 and
 //! `fibonacci_number(1)` = 1. They return `Some` if the result
 //! fits in a `u32`, and `None` otherwise.
+//!
+//! The crate is `no_std` when built without the default `std`
+//! feature. The functions that need an allocator or
+//! floating-point math are then left out.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+pub mod generic;
+pub use generic::{fib, fib_fold_generic, fib_overflow_index, representable_count, FibInt};
 
 /// This is the function by <http://reddit.com/u/findingajobaccount>
 /// in
@@ -12,6 +21,7 @@
 /// that inspired this tour. I've taken the liberty of renaming,
 /// cleaning up the types and interface, fixing Clippy warnings, and
 /// fixing a couple of bugs.
+#[cfg(feature = "std")]
 pub fn fib_vec(n: usize) -> Option<u32> {
     if n == 0 {
         return Some(0);
@@ -23,14 +33,18 @@ pub fn fib_vec(n: usize) -> Option<u32> {
     for i in 2..=n {
         dp.push(dp.get(i - 2).unwrap() + dp.get(i - 1).unwrap());
     }
-    return Some(*dp.last().unwrap());
+    Some(*dp.last().unwrap())
 }
 
 /// This is `fib_vec` with some cleanups to make it a bit more
 /// readable. Thanks to <http://reddit.com/u/YetiBarBar> for the
-/// suggestion to use `with_capacity()`.
+/// suggestion to use `with_capacity()`. The capacity always
+/// has room for the two seeds, and is capped at the 48
+/// values that can fit, so a huge `n` doesn't try a huge
+/// allocation.
+#[cfg(feature = "std")]
 pub fn fib_vec_fancy(n: usize) -> Option<u32> {
-    let mut dp = <Vec<u32>>::with_capacity(n);
+    let mut dp = <Vec<u32>>::with_capacity(n.clamp(1, 47) + 1);
     dp.extend(&[0, 1]);
     for i in 2..=n {
         dp.push(dp[i - 2].checked_add(dp[i - 1])?);
@@ -50,7 +64,7 @@ pub fn fib_array(n: usize) -> Option<u32> {
         return Some(0);
     }
     let mut dp = [0u32, 1];
-    for _ in 0..n - 1 {
+    for _ in 1..n {
         dp = [dp[1], dp[0].checked_add(dp[1])?];
     }
     Some(dp[1])
@@ -61,16 +75,23 @@ pub fn fib_array(n: usize) -> Option<u32> {
 /// of the structure of F(n), anytime the high bit is set
 /// that must be the last value.
 pub fn fib_registered(n: usize) -> Option<u32> {
+    // x is F(2k) and y is F(2k+1). The only Fibonacci Number
+    // with the high bit set that fits is F(47), so a sum with
+    // an operand that has the high bit set must overflow, and
+    // a sum of two operands without it cannot wrap.
     let mut x = 0u32;
     let mut y = 1;
     let hi = !0 ^ (!0 >> 1);
     let mut m = n & !1;
     while m > 0 {
+        // F(2k+2) = F(2k) + F(2k+1) overflows if F(2k+1) is F(47).
         if y & hi == 0 {
             x = x.wrapping_add(y);
         } else {
             break;
         }
+        // F(2k+3) = F(2k+1) + F(2k+2): F(2k+2) is even-indexed
+        // and so never F(47), but check anyway.
         if x & hi == 0 {
             y = y.wrapping_add(x);
         } else {
@@ -79,17 +100,23 @@ pub fn fib_registered(n: usize) -> Option<u32> {
         m -= 2;
     }
     if m > 0 {
+        // Stopped early: F(n) is past F(47).
         None
     } else if n & 1 == 0 {
         Some(x)
     } else if x & hi == 0 {
+        // For odd n, y was computed from an x without the high
+        // bit set, so it did not wrap.
         Some(y)
     } else {
         None
     }
 }
 
-/// A functional implementation can use `fold()`.
+/// A functional implementation can use `fold()`. The state
+/// after i steps is (F(i), F(i-1)), taking F(-1) = 1; see
+/// `fib_state()`.
+#[allow(clippy::manual_try_fold)]
 pub fn fib_fold(n: usize) -> Option<u32> {
     (0..n)
         .fold(Some((0u32, 1u32)), |regs, _| {
@@ -99,9 +126,16 @@ pub fn fib_fold(n: usize) -> Option<u32> {
 }
 
 /// A fancy functional implementation can compute lazily.
-/// This function could just return the iterator of
-/// Fibonacci Numbers that it produces.
+/// `fib_sequence()` returns the iterator of Fibonacci
+/// Numbers, and this just picks one out.
 pub fn fib_lazy(n: usize) -> Option<u32> {
+    fib_sequence().nth(n)
+}
+
+/// The lazy iterator behind `fib_lazy()`. It yields every
+/// Fibonacci Number that fits in a `u32`, then stops. Its
+/// state is (F(i), F(i-1)), just as in `fib_fold()`.
+pub fn fib_sequence() -> impl Iterator<Item = u32> {
     let mut state = Some((0, 1));
     let advance = move || {
         state.map(|(x, y)| {
@@ -109,81 +143,3106 @@ pub fn fib_lazy(n: usize) -> Option<u32> {
             x
         })
     };
-    std::iter::from_fn(advance).nth(n)
+    core::iter::from_fn(advance)
+}
+
+/// The iterator that `fib_lazy()` builds internally, as a
+/// type of its own. It yields every Fibonacci Number that
+/// fits in a `u32`, then stops.
+#[derive(Debug, Clone)]
+pub struct FibIter {
+    // (F(i), F(i-1)) for the next value from the front, and
+    // (F(j), F(j-1)) for the next from the back, with
+    // F(-1) = 1. The values from i through j, `remaining` of
+    // them, are still to come.
+    front: (u32, u32),
+    back: (u32, u32),
+    remaining: usize,
+}
+
+/// Iterate over the Fibonacci Numbers starting with F(0).
+pub fn fib_iter() -> FibIter {
+    let last = FIB_TABLE.len() - 1;
+    FibIter {
+        front: (0, 1),
+        back: (FIB_TABLE[last], FIB_TABLE[last - 1]),
+        remaining: FIB_TABLE.len(),
+    }
+}
+
+impl Iterator for FibIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (x, y) = self.front;
+        // Only the step past F(47) wraps, and then this state
+        // is never used.
+        self.front = (x.wrapping_add(y), x);
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Walking backward uses F(j-2) = F(j) - F(j-1), so `rev()`
+/// starts at F(47) without computing its way up there.
+impl DoubleEndedIterator for FibIter {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (x, y) = self.back;
+        // Only the step past F(0) wraps, and then this state is
+        // never used.
+        self.back = (y, x.wrapping_sub(y));
+        Some(x)
+    }
+}
+
+impl ExactSizeIterator for FibIter {}
+
+impl core::iter::FusedIterator for FibIter {}
+
+/// F(n), calling `cb(i, F(i))` for every i from 0 through n
+/// along the way. If F(n) does not fit in a `u32`, `cb` sees
+/// every value that does and then `None` is returned.
+pub fn fib_with_callback<F: FnMut(usize, u32)>(n: usize, mut cb: F) -> Option<u32> {
+    for (i, f) in fib_enumerate() {
+        cb(i, f);
+        if i == n {
+            return Some(f);
+        }
+    }
+    None
+}
+
+/// Every Fibonacci Number that fits in a `u32`, largest
+/// first: F(47), F(46), ..., F(0). The values come straight
+/// from `FIB_TABLE`, so nothing is recomputed.
+pub fn fib_iter_rev() -> impl Iterator<Item = u32> {
+    FIB_TABLE.iter().rev().copied()
+}
+
+/// Fill `buf` with F(0), F(1), ... without allocating,
+/// stopping at its end or at the last `u32` value. Returns
+/// how many entries were written; the rest of `buf` is left
+/// alone.
+pub fn fib_fill(buf: &mut [u32]) -> usize {
+    let mut written = 0;
+    for (slot, f) in buf.iter_mut().zip(fib_iter()) {
+        *slot = f;
+        written += 1;
+    }
+    written
+}
+
+/// The pairs (n, F(n)), stopping at the last `u32` value.
+pub fn fib_enumerate() -> impl Iterator<Item = (usize, u32)> {
+    fib_iter().enumerate()
 }
 
 /// This
 /// [closed-form](https://en.wikipedia.org/wiki/Fibonacci_number#Closed-form_expression)
 /// implementation is pretty fast. It works for `u32` but won't
 /// work so well for bigger types because floating-point fail.
+/// Through F(47) the approximation is within about 10⁻⁵ of
+/// the true value, so rounding to nearest is exact; the
+/// overflow check is done on the rounded value.
+#[cfg(feature = "std")]
 pub fn fib_closed(n: usize) -> Option<u32> {
+    let f_rounded = closed_form(n)?;
+    if f_rounded <= f64::from(u32::MAX) {
+        Some(f_rounded as u32)
+    } else {
+        None
+    }
+}
+
+/// The largest `n` for which `closed_form()` is trusted to
+/// round to exactly F(n). An `f64` has a 53-bit mantissa, so
+/// consecutive integers stay representable up to about
+/// F(78), but the error in `powf()` grows with `n`. Here
+/// F(71) already comes out one too big, and the error at
+/// F(70) is 0.44, only one ulp short of 0.5, so another libm
+/// might get F(70) wrong too. Stopping at F(68), where the
+/// error is 0.17, leaves a margin of a few ulps.
+#[cfg(feature = "std")]
+const CLOSED_FORM_MAX_INDEX: usize = 68;
+
+/// The closed form itself, before rounding.
+#[cfg(feature = "std")]
+fn closed_form_approx(n: usize) -> f64 {
     const SQRT5: f64 = 2.23606797749979;
     const PHI: f64 = (1.0 + SQRT5) / 2.0;
     const PSI: f64 = 1.0 - PHI;
-    let f_approx = (PHI.powf(n as f64) - PSI.powf(n as f64)) / SQRT5;
-    let f_rounded = (f_approx + 0.5).floor();
-    // 2**32 - 1
-    const MAX_FIB: f64 = 4294967295.0;
-    if f_rounded <= MAX_FIB {
-        Some(f_rounded as u32)
+    (PHI.powf(n as f64) - PSI.powf(n as f64)) / SQRT5
+}
+
+/// The closed form, rounded to nearest, or `None` past the
+/// point where rounding is no longer exact.
+#[cfg(feature = "std")]
+fn closed_form(n: usize) -> Option<f64> {
+    if n > CLOSED_FORM_MAX_INDEX {
+        return None;
+    }
+    Some(closed_form_approx(n).round())
+}
+
+/// How far the closed form in `fib_closed()` is from the true
+/// F(n) before it is rounded. The error is a roughly constant
+/// fraction of F(n), around 10⁻¹⁵, so it grows by about φ
+/// with each step: from 0 at F(0) to about 5 × 10⁻⁶ at F(47),
+/// still far below the 0.5 that would make rounding go wrong.
+/// Panics if F(n) does not fit in a `u32`.
+#[cfg(feature = "std")]
+pub fn fib_closed_error(n: usize) -> f64 {
+    let exact = fib_lookup(n).expect("fib_closed_error: F(n) overflows u32");
+    (closed_form_approx(n) - f64::from(exact)).abs()
+}
+
+/// `fib_closed()` widened to `u64`. Since floating point runs
+/// out of precision long before `u64` runs out of room, this
+/// is `None` for every `n` past 68, rather than a value that
+/// is subtly wrong.
+#[cfg(feature = "std")]
+pub fn fib_closed_u64(n: usize) -> Option<u64> {
+    closed_form(n).map(|f| f as u64)
+}
+
+/// The
+/// [fast doubling](https://www.nayuki.io/page/fast-fibonacci-algorithms)
+/// identities F(2k) = F(k)(2F(k+1) - F(k)) and F(2k+1) =
+/// F(k)² + F(k+1)² let us walk down the bits of `n`, taking
+/// only O(log n) steps. The last step computes only the
+/// value we want, since F(n+1) might not fit.
+pub fn fib_doubling(n: usize) -> Option<u32> {
+    let Some(top) = n.checked_ilog2() else {
+        return Some(0);
+    };
+    // (F(k), F(k+1)) for k the bits of n seen so far.
+    let (mut a, mut b) = (0u32, 1u32);
+    let mut bit = 1 << top;
+    while bit > 1 {
+        let c = a.checked_mul(b.checked_mul(2)? - a)?;
+        let d = a.checked_mul(a)?.checked_add(b.checked_mul(b)?)?;
+        (a, b) = if n & bit == 0 {
+            (c, d)
+        } else {
+            (d, c.checked_add(d)?)
+        };
+        bit >>= 1;
+    }
+    if n & 1 == 0 {
+        a.checked_mul(b.checked_mul(2)? - a)
     } else {
-        None
+        a.checked_mul(a)?.checked_add(b.checked_mul(b)?)
+    }
+}
+
+/// The classic matrix method raises [[1, 1], [1, 0]] to the
+/// `n`-th power by repeated squaring, giving [[F(n+1), F(n)],
+/// [F(n), F(n-1)]]. The arithmetic is done in `u64`, since
+/// F(n+1) may not fit in a `u32` even when F(n) does.
+pub fn fib_matrix(n: usize) -> Option<u32> {
+    type Matrix = [[u64; 2]; 2];
+
+    fn mul(a: &Matrix, b: &Matrix) -> Option<Matrix> {
+        let cell = |i: usize, j: usize| {
+            a[i][0]
+                .checked_mul(b[0][j])?
+                .checked_add(a[i][1].checked_mul(b[1][j])?)
+        };
+        Some([[cell(0, 0)?, cell(0, 1)?], [cell(1, 0)?, cell(1, 1)?]])
+    }
+
+    let mut result: Matrix = [[1, 0], [0, 1]];
+    let mut base: Matrix = [[1, 1], [1, 0]];
+    let mut m = n;
+    while m > 0 {
+        if m & 1 == 1 {
+            result = mul(&result, &base)?;
+        }
+        m >>= 1;
+        if m > 0 {
+            base = mul(&base, &base)?;
+        }
+    }
+    u32::try_from(result[0][1]).ok()
+}
+
+/// Binet's formula without the floating point: work in the
+/// ring ℤ[φ] of numbers a + bφ, where φ² = φ + 1. Then
+/// φⁿ = F(n-1) + F(n)φ exactly, and φⁿ can be found by
+/// repeated squaring using checked integer arithmetic.
+pub fn fib_binet_exact(n: usize) -> Option<u32> {
+    // (a, b) is a + bφ.
+    fn mul((a, b): (u32, u32), (c, d): (u32, u32)) -> Option<(u32, u32)> {
+        let bd = b.checked_mul(d)?;
+        let real = a.checked_mul(c)?.checked_add(bd)?;
+        let phi = a
+            .checked_mul(d)?
+            .checked_add(b.checked_mul(c)?)?
+            .checked_add(bd)?;
+        Some((real, phi))
+    }
+
+    let mut result = (1, 0);
+    let mut base = (0, 1);
+    let mut m = n;
+    while m > 0 {
+        if m & 1 == 1 {
+            result = mul(result, base)?;
+        }
+        m >>= 1;
+        if m > 0 {
+            base = mul(base, base)?;
+        }
+    }
+    Some(result.1)
+}
+
+/// A tail-recursive implementation, with `go()` carrying
+/// (F(i), F(i-1)) along as accumulators. Rust does not
+/// guarantee tail-call optimization, so this is for
+/// illustration only; it stays shallow because the recursion
+/// stops at the first overflow, at most 48 calls down.
+pub fn fib_recursive(n: usize) -> Option<u32> {
+    fn go(n: usize, a: u32, b: u32) -> Option<u32> {
+        if n == 0 {
+            return Some(a);
+        }
+        go(n - 1, a.checked_add(b)?, a)
     }
+    go(n, 0, 1)
 }
 
+/// The infamous doubly-recursive implementation, here to show
+/// what exponential time looks like: computing F(n) this way
+/// takes about F(n) calls. Never call this with a large `n`;
+/// even F(45) takes a long while.
+pub fn fib_naive(n: usize) -> Option<u32> {
+    if n < 2 {
+        return Some(n as u32);
+    }
+    fib_naive(n - 1)?.checked_add(fib_naive(n - 2)?)
+}
+
+/// Define a `const fn` building the table of every Fibonacci
+/// Number that fits in a `$t`, so that the tables are
+/// computed at compile time and can't drift from the
+/// recurrence.
+macro_rules! fib_table_fn {
+    ($name:ident, $t:ty, $len:expr) => {
+        const fn $name() -> [$t; $len] {
+            let mut table: [$t; $len] = [0; $len];
+            table[1] = 1;
+            let mut i = 2;
+            while i < table.len() {
+                table[i] = match table[i - 2].checked_add(table[i - 1]) {
+                    Some(f) => f,
+                    None => panic!("Fibonacci table overflow"),
+                };
+                i += 1;
+            }
+            table
+        }
+    };
+}
+
+fib_table_fn!(build_fib_table, u32, 48);
+fib_table_fn!(build_fib_table_u64, u64, 94);
+fib_table_fn!(build_fib_table_u128, u128, 187);
+
+const FIB_TABLE: [u32; 48] = build_fib_table();
+const FIB_TABLE_U64: [u64; 94] = build_fib_table_u64();
+const FIB_TABLE_U128: [u128; 187] = build_fib_table_u128();
+
+// Fail the build if the tables are ever wrong: check the
+// seeds, the recurrence, and the last entries against values
+// computed independently.
+const _: () = {
+    assert!(FIB_TABLE[0] == 0 && FIB_TABLE[1] == 1);
+    let mut i = 2;
+    while i < FIB_TABLE.len() {
+        assert!(FIB_TABLE[i] == FIB_TABLE[i - 2] + FIB_TABLE[i - 1]);
+        i += 1;
+    }
+    assert!(FIB_TABLE[47] == 2_971_215_073);
+    assert!(FIB_TABLE_U64[93] == 12_200_160_415_121_876_738);
+    assert!(FIB_TABLE_U128[186] == 332_825_110_087_067_562_321_196_029_789_634_457_848);
+};
+
 /// This dumb implementation is what you do if you want
-/// really, really fast Fibonacci Numbers. These values were
-/// computed using a simple Python program, and are likely
-/// to be correct.
+/// really, really fast Fibonacci Numbers. The table is
+/// computed by the compiler.
 pub fn fib_lookup(n: usize) -> Option<u32> {
-    const F: &[u32] = &[
-        0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765,
-        10946, 17711, 28657, 46368, 75025, 121393, 196418, 317811, 514229, 832040, 1346269,
-        2178309, 3524578, 5702887, 9227465, 14930352, 24157817, 39088169, 63245986, 102334155,
-        165580141, 267914296, 433494437, 701408733, 1134903170, 1836311903, 2971215073,
-    ];
-    F.get(n).copied()
+    FIB_TABLE.get(n).copied()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// F(0), ..., F(N - 1) as an array, for tables of any size
+/// that the caller builds at compile time:
+///
+/// ```
+/// const TABLE: [u32; 10] = fib_tour::fib_const_array();
+/// assert_eq!(34, TABLE[9]);
+/// ```
+///
+/// Only F(0) through F(47) fit in a `u32`, so `N` must be at
+/// most 48. This is checked when the function is
+/// instantiated, so a bigger `N` fails to build:
+///
+/// ```compile_fail
+/// let table = fib_tour::fib_const_array::<49>();
+/// ```
+pub const fn fib_const_array<const N: usize>() -> [u32; N] {
+    const { assert!(N <= FIB_TABLE.len(), "fib_const_array: N > 48") };
+    let mut table = [0; N];
+    let mut i = 0;
+    while i < N {
+        table[i] = FIB_TABLE[i];
+        i += 1;
+    }
+    table
+}
 
-    #[test]
-    fn test_fib_lookup() {
-        let fibs = [0, 1, 1, 2, 3, 5, 8];
-        for (i, fib) in fibs.into_iter().enumerate() {
-            assert_eq!(Some(fib), fib_lookup(i));
+/// `fib_lookup()` for all 94 Fibonacci Numbers that fit in a
+/// `u64`.
+pub fn fib_lookup_u64(n: usize) -> Option<u64> {
+    FIB_TABLE_U64.get(n).copied()
+}
+
+/// `fib_lookup()` for all 187 Fibonacci Numbers that fit in a
+/// `u128`.
+pub fn fib_lookup_u128(n: usize) -> Option<u128> {
+    FIB_TABLE_U128.get(n).copied()
+}
+
+/// Fibonacci Numbers at the type level: `Fib::<N>::VALUE` is
+/// F(N), usable anywhere a constant is. Naming the value for
+/// an `N` whose F(N) does not fit in a `u32` fails to compile.
+pub struct Fib<const N: usize>;
+
+impl<const N: usize> Fib<N> {
+    pub const VALUE: u32 = {
+        assert!(N < FIB_TABLE.len(), "Fib::<N>::VALUE overflows u32");
+        FIB_TABLE[N]
+    };
+}
+
+/// When `u32` isn't enough, `fib()` can compute in a wider
+/// type. This gets through F(93).
+pub fn fib_u64(n: usize) -> Option<u64> {
+    fib(n)
+}
+
+/// Going wider still gets through F(186).
+pub fn fib_u128(n: usize) -> Option<u128> {
+    fib(n)
+}
+
+/// The Fibonacci sequence extends backward as the
+/// [negafibonacci](https://en.wikipedia.org/wiki/Generalizations_of_Fibonacci_numbers#Extension_to_negative_integers)
+/// numbers: F(-n) = (-1)^(n+1) F(n). This returns `None` if
+/// the result does not fit in an `i32`.
+pub fn fib_signed(n: i32) -> Option<i32> {
+    let m = n.unsigned_abs() as usize;
+    let f = i32::try_from(fib_array(m)?).ok()?;
+    if n < 0 && m & 1 == 0 {
+        Some(-f)
+    } else {
+        Some(f)
+    }
+}
+
+/// The [Lucas numbers](https://en.wikipedia.org/wiki/Lucas_number)
+/// follow the Fibonacci recurrence, but start with L(0) = 2
+/// and L(1) = 1.
+pub fn lucas(n: usize) -> Option<u32> {
+    gibonacci(2, 1, n)
+}
+
+/// The "Gibonacci" numbers are `fib_array()` with arbitrary
+/// seeds G(0) = `a` and G(1) = `b`.
+pub fn gibonacci(a: u32, b: u32, n: usize) -> Option<u32> {
+    if n == 0 {
+        return Some(a);
+    }
+    let mut dp = [a, b];
+    for _ in 1..n {
+        dp = [dp[1], dp[0].checked_add(dp[1])?];
+    }
+    Some(dp[1])
+}
+
+/// Find `n` such that F(n) is `value`, if there is one.
+/// Since F(1) = F(2) = 1, `fib_index(1)` is ambiguous: the
+/// smaller index 1 is returned.
+pub fn fib_index(value: u32) -> Option<usize> {
+    fib_iter()
+        .take_while(|&f| f <= value)
+        .position(|f| f == value)
+}
+
+/// A number x is a Fibonacci Number exactly when 5x² + 4 or
+/// 5x² - 4 is a perfect square. This is computed in `u128`:
+/// 5x² overflows `u64` for large `u32` values.
+pub fn is_fibonacci(value: u32) -> bool {
+    let is_square = |s: u128| {
+        let r = s.isqrt();
+        r * r == s
+    };
+    let q = 5 * u128::from(value) * u128::from(value);
+    is_square(q + 4) || q.checked_sub(4).is_some_and(is_square)
+}
+
+/// Errors reported by the `Result`-returning functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FibError {
+    /// F(`index`) does not fit in a `u32`.
+    Overflow { index: usize },
+    /// A sequence's entry at `index` is not F(`index`).
+    NotFibonacci { index: usize },
+    /// A string is not a valid index.
+    Parse(ParseError),
+}
+
+impl core::fmt::Display for FibError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            FibError::Overflow { index } => write!(f, "F({}) overflows u32", index),
+            FibError::NotFibonacci { index } => {
+                write!(f, "entry {} is not F({})", index, index)
+            }
+            FibError::Parse(e) => write!(f, "bad index: {}", e),
         }
-        assert_eq!(Some(2_971_215_073), fib_lookup(47));
-        for i in 48..=51 {
-            assert!(fib_lookup(i).is_none());
+    }
+}
+
+impl core::error::Error for FibError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            FibError::Parse(e) => Some(e),
+            _ => None,
         }
-        assert!(fib_lookup(100).is_none());
     }
+}
 
-    fn test_fib(name: &str, f: fn(usize) -> Option<u32>) {
-        for i in 0..=47 {
-            let fi = f(i);
-            let fli = fib_lookup(i);
-            eprintln!("{}: {} {:?} ({:?})", name, i, fi, fli);
-            assert_eq!(fi, fli);
+impl From<ParseError> for FibError {
+    fn from(e: ParseError) -> Self {
+        FibError::Parse(e)
+    }
+}
+
+/// This is `fib_array()` with a `Result` that says what went
+/// wrong, for use with `?`.
+pub fn fib_checked(n: usize) -> Result<u32, FibError> {
+    fib_array(n).ok_or(FibError::Overflow { index: n })
+}
+
+/// Sometimes clamping is better than failing: this returns
+/// `u32::MAX` for any F(n) that does not fit.
+pub fn fib_saturating(n: usize) -> u32 {
+    fib_array(n).unwrap_or(u32::MAX)
+}
+
+/// This computes F(n) mod 2³², giving what the recurrence
+/// with wrapping adds would give but never stopping. It uses
+/// the fast doubling of `fib_doubling()`, so even a huge `n`
+/// takes only O(log n) steps.
+pub fn fib_wrapping(n: usize) -> u32 {
+    fib_wrapping_pair(n).0
+}
+
+/// (F(n), F(n+1)) mod 2³², by the fast doubling identities
+/// with wrapping arithmetic. Arithmetic mod 2³² is a ring, so
+/// the subtraction in F(2k) may wrap too.
+fn fib_wrapping_pair(n: usize) -> (u32, u32) {
+    // (F(k), F(k+1)) for k the bits of n seen so far.
+    let (mut a, mut b) = (0u32, 1u32);
+    let mut bit = match n.checked_ilog2() {
+        Some(top) => 1 << top,
+        None => 0,
+    };
+    while bit > 0 {
+        let c = a.wrapping_mul(b.wrapping_mul(2).wrapping_sub(a));
+        let d = a.wrapping_mul(a).wrapping_add(b.wrapping_mul(b));
+        (a, b) = if n & bit == 0 {
+            (c, d)
+        } else {
+            (d, c.wrapping_add(d))
+        };
+        bit >>= 1;
+    }
+    (a, b)
+}
+
+/// What `fib_policy()` should do when F(n) does not fit in a
+/// `u32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnOverflow {
+    /// Return `None`, like `fib_checked()`.
+    None,
+    /// Return `u32::MAX`, like `fib_saturating()`.
+    Saturate,
+    /// Return F(n) mod 2³², like `fib_wrapping()`.
+    Wrap,
+}
+
+/// The three overflow-handling functions above behind one
+/// entry point, chosen by `policy`. Only `OnOverflow::None`
+/// ever returns `None`.
+pub fn fib_policy(n: usize, policy: OnOverflow) -> Option<u32> {
+    match policy {
+        OnOverflow::None => fib_checked(n).ok(),
+        OnOverflow::Saturate => Some(fib_saturating(n)),
+        OnOverflow::Wrap => Some(fib_wrapping(n)),
+    }
+}
+
+/// Seeds and an overflow policy together, for when
+/// `gibonacci()` and `fib_policy()` are both wanted.
+///
+/// ```
+/// use fib_tour::{FibBuilder, OnOverflow};
+/// let lucas = FibBuilder::new().seeds(2, 1).on_overflow(OnOverflow::Saturate);
+/// assert_eq!(Some(123), lucas.compute(10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FibBuilder {
+    seeds: (u32, u32),
+    on_overflow: OnOverflow,
+}
+
+impl FibBuilder {
+    /// Seeds 0 and 1, returning `None` on overflow: plain
+    /// `fib_array()`.
+    pub fn new() -> Self {
+        FibBuilder {
+            seeds: (0, 1),
+            on_overflow: OnOverflow::None,
         }
-        for i in 48..=51 {
-            let fi = f(i);
-            eprintln!("{}: {} {:?} (None)", name, i, fi);
-            assert!(fi.is_none());
+    }
+
+    /// Start the sequence with G(0) = `a` and G(1) = `b`.
+    pub fn seeds(self, a: u32, b: u32) -> Self {
+        FibBuilder {
+            seeds: (a, b),
+            ..self
         }
     }
 
-    #[test]
-    fn test_fibs() {
-        test_fib("vec", fib_vec);
-        test_fib("fancy", fib_vec_fancy);
-        test_fib("array", fib_array);
-        test_fib("registered", fib_registered);
-        test_fib("fold", fib_fold);
-        test_fib("lazy", fib_lazy);
-        test_fib("closed", fib_closed);
+    /// What to do when G(n) does not fit in a `u32`.
+    pub fn on_overflow(self, policy: OnOverflow) -> Self {
+        FibBuilder {
+            on_overflow: policy,
+            ..self
+        }
+    }
+
+    /// G(n) with these seeds and this policy. Wrapping uses
+    /// G(n) = a·F(n-1) + b·F(n) mod 2³², so like
+    /// `fib_wrapping()` it takes only O(log n) steps.
+    pub fn compute(&self, n: usize) -> Option<u32> {
+        let (a, b) = self.seeds;
+        match self.on_overflow {
+            OnOverflow::None => gibonacci(a, b, n),
+            OnOverflow::Saturate => Some(gibonacci(a, b, n).unwrap_or(u32::MAX)),
+            OnOverflow::Wrap => {
+                let Some(m) = n.checked_sub(1) else {
+                    return Some(a);
+                };
+                let (f, g) = fib_wrapping_pair(m);
+                Some(a.wrapping_mul(f).wrapping_add(b.wrapping_mul(g)))
+            }
+        }
+    }
+}
+
+impl Default for FibBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The length of the
+/// [Pisano period](https://en.wikipedia.org/wiki/Pisano_period)
+/// of `m`: the Fibonacci Numbers mod `m` repeat once the pair
+/// 0, 1 comes around again. The period can be as long as 6m,
+/// so it is returned as a `u64`. Finding it takes that many
+/// steps. Panics if `m` is 0.
+pub fn pisano_period(m: u32) -> u64 {
+    assert!(m > 0, "pisano_period: zero modulus");
+    if m == 1 {
+        return 1;
+    }
+    let m = u64::from(m);
+    let (mut a, mut b) = (0, 1);
+    let mut period = 0;
+    loop {
+        (a, b) = (b, (a + b) % m);
+        period += 1;
+        if a == 0 && b == 1 {
+            return period;
+        }
+    }
+}
+
+/// F(n) mod `m` can be found for any `n` by first reducing
+/// `n` modulo the Pisano period of `m`. Finding the period
+/// takes O(m) steps. Panics if `m` is 0.
+pub fn fib_mod(n: u128, m: u32) -> u32 {
+    assert!(m > 0, "fib_mod: zero modulus");
+    fib_mod_period(n, m, pisano_period(m))
+}
+
+/// `fib_mod()` with the Pisano `period` of `m` already known,
+/// so that callers who needed it too need not find it twice.
+fn fib_mod_period(n: u128, m: u32, period: u64) -> u32 {
+    let k = (n % u128::from(period)) as u64;
+    let m = u64::from(m);
+    let (mut x, mut y) = (0, 1 % m);
+    for _ in 0..k {
+        (x, y) = (y, (x + y) % m);
+    }
+    x as u32
+}
+
+/// Why a string is not a decimal index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string is empty.
+    Empty,
+    /// The character at byte `position` is not a decimal digit.
+    InvalidDigit { position: usize },
+    /// The index does not fit in a `usize`.
+    TooLarge,
+    /// A `FibQuery` opens a parenthesis but does not close it.
+    Unclosed,
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty index"),
+            ParseError::InvalidDigit { position } => {
+                write!(f, "invalid digit at position {}", position)
+            }
+            ParseError::TooLarge => write!(f, "index too large"),
+            ParseError::Unclosed => write!(f, "missing closing parenthesis"),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// `fib_mod()` for an index given as a string of decimal
+/// digits, too long for any integer type. The string is
+/// reduced modulo the Pisano period a digit at a time, just
+/// as in long division. Panics if `m` is 0.
+pub fn fib_mod_bigindex(n_decimal: &str, m: u32) -> Result<u32, ParseError> {
+    assert!(m > 0, "fib_mod_bigindex: zero modulus");
+    if n_decimal.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let period = pisano_period(m);
+    let mut k = 0;
+    for (position, c) in n_decimal.char_indices() {
+        let d = c
+            .to_digit(10)
+            .ok_or(ParseError::InvalidDigit { position })?;
+        k = (k * 10 + u64::from(d)) % period;
+    }
+    Ok(fib_mod_period(u128::from(k), m, period))
+}
+
+/// Parse a string of decimal digits as an index. Unlike
+/// `str::parse()`, a leading `+` is not accepted, and a bad
+/// digit is reported with its position.
+pub fn parse_index(s: &str) -> Result<usize, ParseError> {
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let mut n: usize = 0;
+    for (position, c) in s.char_indices() {
+        let d = c
+            .to_digit(10)
+            .ok_or(ParseError::InvalidDigit { position })?;
+        n = n
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(d as usize))
+            .ok_or(ParseError::TooLarge)?;
+    }
+    Ok(n)
+}
+
+/// F(n) for `n` given as a string, with any problem reported
+/// as a `FibError`.
+pub fn fib_from_str(s: &str) -> Result<u32, FibError> {
+    fib_checked(parse_index(s)?)
+}
+
+/// A request for F(`index`), parsed from `"F(10)"`,
+/// `"fib(10)"`, `"fib 10"`, or just `"10"`.
+///
+/// ```
+/// let query: fib_tour::FibQuery = "F(10)".parse().unwrap();
+/// assert_eq!(Some(55), query.evaluate());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FibQuery {
+    pub index: usize,
+}
+
+impl FibQuery {
+    /// F(`index`), or `None` if it does not fit in a `u32`.
+    pub fn evaluate(&self) -> Option<u32> {
+        fib_lookup(self.index)
+    }
+}
+
+impl core::str::FromStr for FibQuery {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let forms = [("F(", ")"), ("fib(", ")"), ("fib ", "")];
+        let form = forms
+            .iter()
+            .find_map(|&(open, close)| Some((open.len(), close, s.strip_prefix(open)?)));
+        let (offset, digits) = match form {
+            Some((offset, close, rest)) => (
+                offset,
+                rest.strip_suffix(close).ok_or(ParseError::Unclosed)?,
+            ),
+            None => (0, s),
+        };
+        // Report bad digits by their position in all of `s`.
+        let index = parse_index(digits).map_err(|e| match e {
+            ParseError::InvalidDigit { position } => ParseError::InvalidDigit {
+                position: offset + position,
+            },
+            e => e,
+        })?;
+        Ok(FibQuery { index })
+    }
+}
+
+/// A memo table of Fibonacci Numbers, grown on demand. Once
+/// F(n) has been computed, asking for it again is O(1).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct FibCache {
+    fibs: Vec<u32>,
+}
+
+#[cfg(feature = "std")]
+impl FibCache {
+    pub fn new() -> Self {
+        FibCache { fibs: vec![0, 1] }
+    }
+
+    /// The Fibonacci Numbers computed so far, starting with F(0).
+    pub fn values(&self) -> &[u32] {
+        &self.fibs
+    }
+
+    pub fn get(&mut self, n: usize) -> Option<u32> {
+        while self.fibs.len() <= n {
+            let k = self.fibs.len();
+            let f = self.fibs[k - 2].checked_add(self.fibs[k - 1])?;
+            self.fibs.push(f);
+        }
+        Some(self.fibs[n])
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for FibCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// F(`start`) through F(`end`) inclusive, computed in a
+/// single pass. Entries past the overflow point are `None`.
+#[cfg(feature = "std")]
+pub fn fib_range(start: usize, end: usize) -> Vec<Option<u32>> {
+    if start > end {
+        return Vec::new();
+    }
+    let mut fibs = fib_iter().skip(start);
+    (start..=end).map(|_| fibs.next()).collect()
+}
+
+/// The [Zeckendorf representation](https://en.wikipedia.org/wiki/Zeckendorf%27s_theorem)
+/// of `value`: the unique set of non-consecutive Fibonacci
+/// Numbers summing to it, largest first. The greedy algorithm
+/// finds it.
+#[cfg(feature = "std")]
+pub fn zeckendorf(value: u32) -> Vec<u32> {
+    let mut rest = value;
+    let mut terms = Vec::new();
+    while rest > 0 {
+        let (_, f) = greatest_fib_le(rest);
+        terms.push(f);
+        rest -= f;
+    }
+    terms
+}
+
+/// The largest index `a` with F(a) ≤ `value`, and F(a): the
+/// greedy step of `zeckendorf()`. For the value 1 this is
+/// F(2) rather than F(1).
+pub fn greatest_fib_le(value: u32) -> (usize, u32) {
+    // F(0) = 0 is always included, so the point is at least 1.
+    let a = FIB_TABLE.partition_point(|&f| f <= value) - 1;
+    (a, FIB_TABLE[a])
+}
+
+/// The [Fibonacci coding](https://en.wikipedia.org/wiki/Fibonacci_coding)
+/// of a positive `value`: bit `i` says whether F(i + 2) is in
+/// its Zeckendorf representation, and a final extra 1 makes
+/// the codeword end in "11". 0 has no codeword, so its
+/// encoding is empty.
+#[cfg(feature = "std")]
+pub fn fib_encode(value: u32) -> Vec<bool> {
+    let mut bits = Vec::new();
+    let mut rest = value;
+    for (i, &f) in FIB_TABLE.iter().enumerate().skip(2).rev() {
+        if f <= rest {
+            if bits.is_empty() {
+                bits = vec![false; i - 1];
+            }
+            bits[i - 2] = true;
+            rest -= f;
+        }
+    }
+    if !bits.is_empty() {
+        bits.push(true);
+    }
+    bits
+}
+
+/// The length of `fib_encode(value)`, without building it.
+/// If F(a) is the largest term in the Zeckendorf
+/// representation, the codeword has bits for F(2) through
+/// F(a), plus the final 1: a bits in all.
+pub fn fib_code_length(value: u32) -> usize {
+    match value {
+        0 => 0,
+        _ => greatest_fib_le(value).0,
+    }
+}
+
+/// Decode a single Fibonacci codeword. Returns `None` unless
+/// `bits` ends in the first "11" it contains, or if the value
+/// does not fit in a `u32`.
+pub fn fib_decode(bits: &[bool]) -> Option<u32> {
+    let (&last, data) = bits.split_last()?;
+    if !last || data.last() != Some(&true) {
+        return None;
+    }
+    if data.windows(2).any(|w| w[0] && w[1]) {
+        return None;
+    }
+    let mut value = 0u32;
+    for (i, &bit) in data.iter().enumerate() {
+        if bit {
+            value = value.checked_add(fib_lookup(i + 2)?)?;
+        }
+    }
+    Some(value)
+}
+
+/// F(0) + F(1) + ... + F(n) is F(n + 2) - 1, which overflows
+/// exactly when F(n + 2) does.
+pub fn fib_sum(n: usize) -> Option<u32> {
+    Some(fib_array(n.checked_add(2)?)? - 1)
+}
+
+/// The [k-bonacci numbers](https://en.wikipedia.org/wiki/Generalizations_of_Fibonacci_numbers#Fibonacci_numbers_of_higher_order)
+/// start with k - 1 zeros and a one; each later term is the
+/// sum of the previous `k`. `kbonacci(2, n)` is F(n), and
+/// `kbonacci(3, n)` is the Tribonacci sequence. Panics if `k`
+/// is 0.
+#[cfg(feature = "std")]
+pub fn kbonacci(k: usize, n: usize) -> Option<u32> {
+    assert!(k > 0, "kbonacci: k must be positive");
+    // Each term is just the one before: 1 forever.
+    if k == 1 {
+        return Some(1);
+    }
+    if n < k - 1 {
+        return Some(0);
+    }
+    // For large k the window never drops its 1 before the
+    // terms 1, 1, 2, 4, ... overflow, so skip the window.
+    if k > 33 {
+        return match n - (k - 1) {
+            0 => Some(1),
+            j => u32::try_from(j - 1).ok().and_then(|s| 1u32.checked_shl(s)),
+        };
+    }
+    // The last k terms, as a ring starting at `oldest`, and
+    // their sum, which is the next term.
+    let mut window = vec![0u32; k];
+    window[k - 1] = 1;
+    let mut oldest = 0;
+    let mut sum = Some(1u32);
+    for _ in k - 1..n {
+        let next = sum?;
+        sum = (next - window[oldest]).checked_add(next);
+        window[oldest] = next;
+        oldest = (oldest + 1) % k;
+    }
+    Some(window[(oldest + k - 1) % k])
+}
+
+/// The consecutive pair (F(n), F(n + 1)), computed in one
+/// pass. This is `None` if F(n + 1) does not fit in a `u32`.
+pub fn fib_pair(n: usize) -> Option<(u32, u32)> {
+    let (mut x, mut y) = (0u32, 1u32);
+    for _ in 0..n {
+        (x, y) = (y, x.checked_add(y)?);
+    }
+    Some((x, y))
+}
+
+/// The pair (F(n), F(n-1)), with F(-1) = 1: the state that
+/// `fib_fold()` and `fib_sequence()` both carry after n
+/// steps. It is `fib_pair(n - 1)` the other way round.
+pub fn fib_state(n: usize) -> Option<(u32, u32)> {
+    match n.checked_sub(1) {
+        None => Some((0, 1)),
+        Some(m) => fib_pair(m).map(|(x, y)| (y, x)),
+    }
+}
+
+/// F(n + 1) / F(n) converges to the golden ratio φ. This is
+/// `None` for n = 0, and when F(n + 1) does not fit in a `u32`.
+pub fn fib_ratio(n: usize) -> Option<f64> {
+    if n == 0 {
+        return None;
+    }
+    let (f, g) = fib_pair(n)?;
+    Some(f64::from(g) / f64::from(f))
+}
+
+/// `fib_ratio()` as an exact fraction: (F(n + 1), F(n)),
+/// which is already in lowest terms since gcd(F(n + 1), F(n))
+/// = F(gcd(n + 1, n)) = F(1) = 1. These are the convergents
+/// of the continued fraction [1; 1, 1, ...] for φ.
+pub fn golden_convergent(n: usize) -> Option<(u32, u32)> {
+    if n == 0 {
+        return None;
+    }
+    let (f, g) = fib_pair(n)?;
+    Some((g, f))
+}
+
+/// F(2n) straight from F(n) and F(n + 1), using the identity
+/// F(2n) = F(n) (2F(n + 1) - F(n)): the step `fib_doubling()`
+/// repeats.
+pub fn fib_double_index(n: usize) -> Option<u32> {
+    let (f, g) = fib_pair(n)?;
+    f.checked_mul(g.checked_mul(2)? - f)
+}
+
+/// F(n + 1) divided by F(n), as (quotient, remainder). Since
+/// F(n + 1) = F(n) + F(n - 1) and F(n - 1) < F(n) from n = 3
+/// on, that is always (1, F(n - 1)) there. This is `None` for
+/// n = 0 and when F(n + 1) does not fit in a `u32`.
+pub fn fib_div_consecutive(n: usize) -> Option<(u32, u32)> {
+    if n == 0 {
+        return None;
+    }
+    let (f, g) = fib_pair(n)?;
+    Some((g / f, g % f))
+}
+
+/// L(n) / F(n) converges to √5, from alternate sides. This
+/// is `None` for n = 0, and when L(n) does not fit in a `u32`,
+/// which happens at n = 47, one index before F(n) overflows.
+pub fn lucas_fib_ratio(n: usize) -> Option<f64> {
+    if n == 0 {
+        return None;
+    }
+    Some(f64::from(lucas(n)?) / f64::from(fib_array(n)?))
+}
+
+/// Compute many Fibonacci Numbers at once. The indices are
+/// taken in groups of `LANES`, and each group's states are
+/// advanced in lockstep in plain arrays: the compiler can
+/// turn the inner loop into SIMD instructions. Whether this
+/// beats a plain loop depends on the target's vector width:
+/// compare with `RUSTFLAGS="-C target-cpu=native" cargo bench`.
+/// The results are the same as mapping `fib_array()` over
+/// `indices`.
+#[cfg(feature = "std")]
+pub fn fib_batch(indices: &[usize]) -> Vec<Option<u32>> {
+    const LANES: usize = 8;
+    let fits = |i: usize| i < FIB_TABLE.len();
+    let mut result = Vec::with_capacity(indices.len());
+    for chunk in indices.chunks(LANES) {
+        let mut n = [u32::MAX; LANES];
+        for (l, &i) in chunk.iter().enumerate() {
+            if fits(i) {
+                n[l] = i as u32;
+            }
+        }
+        let steps = chunk.iter().copied().filter(|&i| fits(i)).max();
+        let mut x = [0u32; LANES];
+        let mut y = [1u32; LANES];
+        let mut out = [0u32; LANES];
+        for j in 0..=steps.unwrap_or(0) as u32 {
+            for l in 0..LANES {
+                out[l] = if n[l] == j { x[l] } else { out[l] };
+                // y wraps once x reaches F(47), but by then
+                // every lane's x has been read.
+                let z = x[l].wrapping_add(y[l]);
+                x[l] = y[l];
+                y[l] = z;
+            }
+        }
+        result.extend(chunk.iter().zip(out).map(|(&i, f)| fits(i).then_some(f)));
+    }
+    result
+}
+
+/// A Fibonacci Number together with its index, for display.
+/// It prints as `F(10) = 55`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FibNumber {
+    pub index: usize,
+    pub value: u32,
+}
+
+impl FibNumber {
+    /// F(`n`), if it fits in a `u32`.
+    pub fn new(n: usize) -> Option<FibNumber> {
+        Some(FibNumber {
+            index: n,
+            value: fib_array(n)?,
+        })
+    }
+
+    /// The value with commas between groups of three digits,
+    /// as in `2,971,215,073`.
+    #[cfg(feature = "std")]
+    pub fn grouped(&self) -> String {
+        let digits = self.value.to_string();
+        let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                result.push(',');
+            }
+            result.push(c);
+        }
+        result
+    }
+}
+
+impl core::fmt::Display for FibNumber {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "F({}) = {}", self.index, self.value)
+    }
+}
+
+/// The Fibonacci Number after `value`, which is about φ times
+/// as big, or `None` if `value` is not a Fibonacci Number or
+/// the next one does not fit in a `u32`. The value 1 is taken
+/// as F(2), so its successor is 2.
+pub fn golden_next(value: u32) -> Option<u32> {
+    match greatest_fib_le(value) {
+        (a, f) if f == value => fib_lookup(a + 1),
+        _ => None,
+    }
+}
+
+/// The Fibonacci Number closest to `value`. Ties go to the
+/// smaller one, so `nearest_fibonacci(4)` is 3.
+pub fn nearest_fibonacci(value: u32) -> u32 {
+    let i = FIB_TABLE.partition_point(|&f| f < value);
+    match FIB_TABLE.get(i) {
+        None => FIB_TABLE[FIB_TABLE.len() - 1],
+        Some(&hi) if i == 0 => hi,
+        Some(&hi) => {
+            let lo = FIB_TABLE[i - 1];
+            if hi - value < value - lo {
+                hi
+            } else {
+                lo
+            }
+        }
+    }
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// gcd(F(m), F(n)) is F(gcd(m, n)), so one small Fibonacci
+/// evaluation does the job. This works even when F(m) and
+/// F(n) themselves are far too big for a `u32`.
+pub fn fib_gcd(m: usize, n: usize) -> Option<u32> {
+    fib_array(gcd(m, n))
+}
+
+/// gcd(F(`a`), F(`b`)) again, but computed from the values
+/// themselves when both fit in a `u32`, and by the identity
+/// in `fib_gcd()` otherwise. The two routes always agree.
+pub fn gcd_of_fibs(a: usize, b: usize) -> Option<u32> {
+    match (fib_lookup(a), fib_lookup(b)) {
+        (Some(x), Some(y)) => Some(gcd(x as usize, y as usize) as u32),
+        _ => fib_gcd(a, b),
+    }
+}
+
+/// The exact decimal digits of F(n), for any `n`, using
+/// grade-school addition on arrays of digits. Each step is
+/// linear in the number of digits, so this is O(n²) overall.
+#[cfg(feature = "std")]
+pub fn fib_decimal_string(n: usize) -> String {
+    // Little-endian decimal digits of F(i) and F(i+1).
+    let mut x = vec![0u8];
+    let mut y = vec![1u8];
+    for _ in 0..n {
+        let mut z = Vec::with_capacity(y.len() + 1);
+        let mut carry = 0;
+        for (i, &d) in y.iter().enumerate() {
+            let sum = x.get(i).copied().unwrap_or(0) + d + carry;
+            z.push(sum % 10);
+            carry = sum / 10;
+        }
+        if carry > 0 {
+            z.push(carry);
+        }
+        x = std::mem::replace(&mut y, z);
+    }
+    x.iter().rev().map(|&d| char::from(b'0' + d)).collect()
+}
+
+/// Write the first `count` Fibonacci Numbers to `w`, one
+/// per line, without collecting them. Writing stops early,
+/// without error, at the last `u32` value.
+#[cfg(feature = "std")]
+pub fn write_fib_sequence<W: std::io::Write>(w: &mut W, count: usize) -> std::io::Result<()> {
+    for f in fib_iter().take(count) {
+        writeln!(w, "{}", f)?;
+    }
+    Ok(())
+}
+
+/// The Fibonacci Numbers F(0), F(1), ... as a vector. Use
+/// `try_from()` to check that a vector from elsewhere really
+/// is a prefix of the sequence.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FibSequence(pub Vec<u32>);
+
+#[cfg(feature = "std")]
+impl FibSequence {
+    /// Every Fibonacci Number that fits in a `u32`.
+    pub fn new() -> Self {
+        FibSequence(FIB_TABLE.to_vec())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for FibSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The index of the first entry of `values` that is not the
+/// corresponding Fibonacci Number.
+fn first_non_fib(values: &[u32]) -> Option<usize> {
+    values
+        .iter()
+        .enumerate()
+        .position(|(i, &v)| fib_lookup(i) != Some(v))
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<Vec<u32>> for FibSequence {
+    type Error = FibError;
+
+    fn try_from(values: Vec<u32>) -> Result<Self, FibError> {
+        match first_non_fib(&values) {
+            Some(index) => Err(FibError::NotFibonacci { index }),
+            None => Ok(FibSequence(values)),
+        }
+    }
+}
+
+/// Whether `values` is F(0), F(1), ..., F(k) for some `k`:
+/// that is, it starts 0, 1 and each later entry is the sum of
+/// the two before it. A prefix may have any length, so the
+/// empty slice and `[0]` count; more than 48 entries cannot,
+/// since F(48) does not fit in a `u32`.
+pub fn validate_fib_prefix(values: &[u32]) -> bool {
+    first_non_fib(values).is_none()
+}
+
+/// The even Fibonacci Numbers 2, 8, 34, ... that fit in a
+/// `u32`. Every third Fibonacci Number is even, and the even
+/// ones follow their own recurrence E(k) = 4E(k-1) + E(k-2),
+/// so the odd ones need never be computed.
+fn even_fib_iter() -> impl Iterator<Item = u32> {
+    let step = |&(a, b): &(u32, u32)| Some((b, b.checked_mul(4)?.checked_add(a)?));
+    core::iter::successors(Some((0, 2)), step).map(|(_, b)| b)
+}
+
+/// A `u32` that remembers whether it has overflowed, so that
+/// `sum()` over a pipeline of Fibonacci Numbers gives `None`
+/// instead of panicking or wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckedU32(pub Option<u32>);
+
+impl CheckedU32 {
+    pub fn some(value: u32) -> Self {
+        CheckedU32(Some(value))
+    }
+}
+
+impl core::iter::Sum for CheckedU32 {
+    fn sum<I: Iterator<Item = CheckedU32>>(mut iter: I) -> Self {
+        CheckedU32(iter.try_fold(0u32, |total, CheckedU32(v)| total.checked_add(v?)))
+    }
+}
+
+/// The sum of the even Fibonacci Numbers less than `limit`,
+/// as in [Project Euler Problem 2](https://projecteuler.net/problem=2).
+/// The sum of all the even ones that fit is less than
+/// `u32::MAX`, so this cannot overflow.
+pub fn even_fib_sum_below(limit: u32) -> u32 {
+    even_fib_iter().take_while(|&e| e < limit).sum()
+}
+
+/// The first `count` even Fibonacci Numbers after F(0) = 0,
+/// or all of them that fit in a `u32` if there are fewer.
+#[cfg(feature = "std")]
+pub fn even_fibs(count: usize) -> Vec<u32> {
+    even_fib_iter().take(count).collect()
+}
+
+/// Map `fib_array()` over `indices`, splitting the work
+/// across one scoped thread per available core. The results
+/// are in the same order as `indices`. For indices that fit
+/// in a `u32` the work is so small that this is mostly a
+/// demonstration of the pattern.
+#[cfg(feature = "std")]
+pub fn fib_batch_parallel(indices: &[usize]) -> Vec<Option<u32>> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = indices.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = indices
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(|&i| fib_array(i)).collect::<Vec<_>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    })
+}
+
+/// A sanity wrapper for trying out a new implementation `f`:
+/// in debug builds, it checks `f(n)` against `fib_lookup(n)`
+/// and panics on a mismatch. In release builds the check
+/// compiles away and this is just `f(n)`.
+pub fn fib_verified(n: usize, f: fn(usize) -> Option<u32>) -> Option<u32> {
+    let result = f(n);
+    debug_assert_eq!(fib_lookup(n), result, "fib_verified: F({}) mismatch", n);
+    result
+}
+
+/// How many distinct Fibonacci values lie in `lo..=hi`. The
+/// value 1 is counted once, even though it is both F(1) and
+/// F(2).
+pub fn fib_count_in_range(lo: u32, hi: u32) -> usize {
+    // Skip F(1), the first copy of 1.
+    FIB_TABLE
+        .iter()
+        .enumerate()
+        .filter(|&(i, f)| i != 1 && (lo..=hi).contains(f))
+        .count()
+}
+
+/// The largest `n` for which the `u32` functions return
+/// `Some`, found by running the recurrence until it overflows.
+pub fn max_representable_index() -> usize {
+    fib_overflow_index::<u32>() - 1
+}
+
+/// The `k`-th Fibonacci Number counting down from the
+/// largest that fits in a `u32`: `fib_from_end(0)` is F(47).
+pub fn fib_from_end(k: usize) -> Option<u32> {
+    fib_lookup(max_representable_index().checked_sub(k)?)
+}
+
+/// log₁₀ φ and log₁₀ √5 as 192-bit binary fractions, most
+/// significant 64 bits first.
+const LOG10_PHI_FIXED: [u64; 3] = [0x358036c82451b7f3, 0x65d3db23845599f5, 0x887a5e47e9bdd71c];
+const LOG10_SQRT5_FIXED: [u64; 3] = [0x5977d95ec10c0219, 0xdc1da994fd20dba1, 0xf654b3ceaf0b832d];
+
+/// The number of decimal digits in F(n), from the closed
+/// form: F(n) is the nearest integer to φⁿ/√5, so for n ≥ 2
+/// it has ⌊n log₁₀ φ - log₁₀ √5⌋ + 1 digits. An `f64` gets
+/// this wrong once `n` is in the trillions, so the product
+/// is done in 192-bit fixed point instead. That keeps the
+/// error under 2⁻¹²⁸ for any `usize` index, so only an `n`
+/// putting n log₁₀ φ - log₁₀ √5 that close to a whole number
+/// could come out wrong. The indices with F(n) in a `u128`
+/// are counted from `FIB_TABLE_U128`, since for those the
+/// φ^-n part of the closed form is not yet negligible.
+pub fn fib_digit_count(n: usize) -> usize {
+    const MASK: u128 = u64::MAX as u128;
+    if let Some(&f) = FIB_TABLE_U128.get(n) {
+        return f.checked_ilog10().map_or(1, |d| d as usize + 1);
+    }
+    // n log₁₀ φ, as a whole part and a 192-bit fraction.
+    let n = n as u128;
+    let [l2, l1, l0] = LOG10_PHI_FIXED.map(u128::from);
+    let (p2, p1, p0) = (n * l2, n * l1, n * l0);
+    let t1 = (p1 & MASK) + (p0 >> 64);
+    let t2 = (p2 & MASK) + (p1 >> 64) + (t1 >> 64);
+    let whole = (p2 >> 64) + (t2 >> 64);
+    let fraction = [t2 & MASK, t1 & MASK, p0 & MASK].map(|w| w as u64);
+    // Subtract log₁₀ √5 < 1, borrowing from the whole part if
+    // need be.
+    let borrow = fraction < LOG10_SQRT5_FIXED;
+    (whole - u128::from(borrow)) as usize + 1
+}
+
+/// 1/F(1) + 1/F(2) + ... + 1/F(`terms`), approaching the
+/// [reciprocal Fibonacci constant](https://en.wikipedia.org/wiki/Reciprocal_Fibonacci_constant)
+/// 3.3598856662... The Fibonacci Numbers are run in `f64`
+/// rather than `u32`, so any number of terms works: past
+/// about 80 terms they add nothing anyway.
+pub fn reciprocal_fib_sum(terms: usize) -> f64 {
+    let (mut x, mut y) = (1.0, 1.0);
+    let mut sum = 0.0;
+    for _ in 0..terms {
+        sum += 1.0 / x;
+        (x, y) = (y, x + y);
+    }
+    sum
+}
+
+/// The smallest `n` for which F(n) has at least `k` decimal
+/// digits, found by binary search on `fib_digit_count()`,
+/// which never decreases as `n` grows. Panics if `k` is more
+/// than `fib_digit_count(usize::MAX)`, since then the answer
+/// does not fit in a `usize`.
+pub fn first_fib_with_digits(k: usize) -> usize {
+    assert!(
+        k <= fib_digit_count(usize::MAX),
+        "first_fib_with_digits: index would not fit in a usize"
+    );
+    // The answer is in lo..=hi.
+    let (mut lo, mut hi) = (0, usize::MAX);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if fib_digit_count(mid) >= k {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+/// The first `count` distinct values in the union of the
+/// Fibonacci and Lucas numbers, in increasing order. Both
+/// sequences are already sorted (taking L(1) = 1 before
+/// L(0) = 2), so a two-way merge does the job.
+#[cfg(feature = "std")]
+pub fn fib_lucas_merged(count: usize) -> Vec<u32> {
+    let mut fibs = fib_iter().peekable();
+    let mut lucases = [1, 2].into_iter().chain((2..).map_while(lucas)).peekable();
+    let mut merged: Vec<u32> = Vec::new();
+    while merged.len() < count {
+        let next = match (fibs.peek(), lucases.peek()) {
+            (Some(&f), Some(&l)) if f <= l => fibs.next(),
+            (Some(_), Some(_)) => lucases.next(),
+            (Some(_), None) => fibs.next(),
+            (None, _) => lucases.next(),
+        };
+        let Some(v) = next else {
+            break;
+        };
+        if merged.last() != Some(&v) {
+            merged.push(v);
+        }
+    }
+    merged
+}
+
+/// Estimate the real-valued `n` with F(n) ≈ `value`, by
+/// inverting F(n) ≈ φⁿ/√5: n ≈ ln(value √5) / ln φ. For a
+/// Fibonacci Number this rounds to its index (taking 2 for
+/// the value 1); for 0 it is negative infinity.
+#[cfg(feature = "std")]
+pub fn fib_index_approx(value: u32) -> f64 {
+    const SQRT5: f64 = 2.23606797749979;
+    const PHI: f64 = (1.0 + SQRT5) / 2.0;
+    (f64::from(value) * SQRT5).ln() / PHI.ln()
+}
+
+/// An index `n` whose F(n) is known to fit in a `u32`, so
+/// that the value can be had without a check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FibIndex(usize);
+
+impl FibIndex {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    pub fn value(&self) -> u32 {
+        FIB_TABLE[self.0]
+    }
+}
+
+impl TryFrom<usize> for FibIndex {
+    type Error = FibError;
+
+    fn try_from(n: usize) -> Result<Self, FibError> {
+        if n < FIB_TABLE.len() {
+            Ok(FibIndex(n))
+        } else {
+            Err(FibError::Overflow { index: n })
+        }
+    }
+}
+
+/// F(0)² + F(1)² + ... + F(n)² is F(n) F(n + 1), so one
+/// `fib_pair()` does the job.
+pub fn fib_sum_of_squares(n: usize) -> Option<u32> {
+    let (f, g) = fib_pair(n)?;
+    f.checked_mul(g)
+}
+
+/// The number of division steps the Euclidean algorithm
+/// takes to find gcd(`a`, `b`). The larger argument is
+/// divided first, so the order of the arguments does not
+/// matter.
+pub fn euclid_steps(a: u32, b: u32) -> usize {
+    let (mut a, mut b) = (a.max(b), a.min(b));
+    let mut steps = 0;
+    while b != 0 {
+        (a, b) = (b, a % b);
+        steps += 1;
+    }
+    steps
+}
+
+/// By [Lamé's theorem](https://en.wikipedia.org/wiki/Euclidean_algorithm#Worst-case),
+/// the smallest inputs that make the Euclidean algorithm take
+/// `steps` steps are consecutive Fibonacci Numbers:
+/// (F(steps + 1), F(steps + 2)), or (F(0), F(1)) for no steps
+/// at all. This is `None` if they don't fit in a `u32`.
+pub fn worst_case_euclid_inputs(steps: usize) -> Option<(u32, u32)> {
+    if steps == 0 {
+        return Some((0, 1));
+    }
+    fib_pair(steps.checked_add(1)?)
+}
+
+/// A table of the first `count` Fibonacci Numbers, or all
+/// of them that fit in a `u32` if there are fewer, with the
+/// columns right-aligned to fit the widest entry.
+///
+/// ```
+/// let table = fib_tour::fib_table_string(5);
+/// assert_eq!(table, "n | F(n)\n0 |    0\n1 |    1\n2 |    1\n3 |    2\n4 |    3\n");
+/// ```
+#[cfg(feature = "std")]
+pub fn fib_table_string(count: usize) -> String {
+    let rows: Vec<(usize, u32)> = fib_enumerate().take(count).collect();
+    let n_width = rows.last().map_or(1, |(n, _)| n.to_string().len());
+    let f_width = rows
+        .iter()
+        .map(|(_, f)| f.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("F(n)".len());
+    let mut table = format!("{:>n_width$} | {:>f_width$}\n", "n", "F(n)");
+    for (n, f) in rows {
+        table += &format!("{:>n_width$} | {:>f_width$}\n", n, f);
+    }
+    table
+}
+
+/// The digital root of F(n): its digits summed over and over
+/// until one is left. That is F(n) mod 9, with 9 in place of
+/// 0, and F(n) mod 9 repeats every 24 steps, so this works
+/// for any `n`. The one exception to the cycle is F(0) = 0,
+/// whose root is 0 where F(24) = 46368 has 9.
+pub fn fib_digital_root(n: usize) -> u32 {
+    const ROOTS: [u32; 24] = [
+        9, 1, 1, 2, 3, 5, 8, 4, 3, 7, 1, 8, 9, 8, 8, 7, 6, 4, 1, 5, 6, 2, 8, 1,
+    ];
+    if n == 0 {
+        return 0;
+    }
+    ROOTS[n % 24]
+}
+
+/// Fibonacci parity goes even, odd, odd, repeating, so F(n)
+/// is even exactly when 3 divides `n`. This works for any
+/// `n` without computing F(n).
+pub fn fib_is_even(n: usize) -> bool {
+    n.is_multiple_of(3)
+}
+
+/// The tour's implementations of `fn(usize) -> Option<u32>`,
+/// so that one can be chosen by value. `fib_naive()` is left
+/// out: it is far too slow to run on every index. Some
+/// variants exist only with the `std` feature, so the enum is
+/// non-exhaustive: turning `std` on must not break a `match`
+/// written without it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FibMethod {
+    #[cfg(feature = "std")]
+    Vec,
+    #[cfg(feature = "std")]
+    VecFancy,
+    Array,
+    Registered,
+    Fold,
+    Lazy,
+    #[cfg(feature = "std")]
+    Closed,
+    Doubling,
+    Matrix,
+    BinetExact,
+    Recursive,
+    Lookup,
+}
+
+impl FibMethod {
+    /// Every method, in tour order.
+    pub const ALL: &'static [FibMethod] = &[
+        #[cfg(feature = "std")]
+        FibMethod::Vec,
+        #[cfg(feature = "std")]
+        FibMethod::VecFancy,
+        FibMethod::Array,
+        FibMethod::Registered,
+        FibMethod::Fold,
+        FibMethod::Lazy,
+        #[cfg(feature = "std")]
+        FibMethod::Closed,
+        FibMethod::Doubling,
+        FibMethod::Matrix,
+        FibMethod::BinetExact,
+        FibMethod::Recursive,
+        FibMethod::Lookup,
+    ];
+
+    /// A short lowercase name, as used by the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "std")]
+            FibMethod::Vec => "vec",
+            #[cfg(feature = "std")]
+            FibMethod::VecFancy => "fancy",
+            FibMethod::Array => "array",
+            FibMethod::Registered => "registered",
+            FibMethod::Fold => "fold",
+            FibMethod::Lazy => "lazy",
+            #[cfg(feature = "std")]
+            FibMethod::Closed => "closed",
+            FibMethod::Doubling => "doubling",
+            FibMethod::Matrix => "matrix",
+            FibMethod::BinetExact => "binet",
+            FibMethod::Recursive => "recursive",
+            FibMethod::Lookup => "lookup",
+        }
+    }
+
+    /// The method with the given `name()`, if any.
+    pub fn from_name(name: &str) -> Option<FibMethod> {
+        FibMethod::ALL.iter().copied().find(|m| m.name() == name)
+    }
+
+    /// The function that implements this method.
+    pub fn function(self) -> fn(usize) -> Option<u32> {
+        match self {
+            #[cfg(feature = "std")]
+            FibMethod::Vec => fib_vec,
+            #[cfg(feature = "std")]
+            FibMethod::VecFancy => fib_vec_fancy,
+            FibMethod::Array => fib_array,
+            FibMethod::Registered => fib_registered,
+            FibMethod::Fold => fib_fold,
+            FibMethod::Lazy => fib_lazy,
+            #[cfg(feature = "std")]
+            FibMethod::Closed => fib_closed,
+            FibMethod::Doubling => fib_doubling,
+            FibMethod::Matrix => fib_matrix,
+            FibMethod::BinetExact => fib_binet_exact,
+            FibMethod::Recursive => fib_recursive,
+            FibMethod::Lookup => fib_lookup,
+        }
+    }
+}
+
+/// F(n) computed by `method`.
+pub fn fib_with(method: FibMethod, n: usize) -> Option<u32> {
+    method.function()(n)
+}
+
+/// F(n) by every method, so that any disagreement between
+/// them shows up at a glance.
+#[cfg(feature = "std")]
+pub fn fib_all(n: usize) -> Vec<(FibMethod, Option<u32>)> {
+    FibMethod::ALL
+        .iter()
+        .map(|&m| (m, fib_with(m, n)))
+        .collect()
+}
+
+/// Every pair of methods whose F(n) differ, each pair once
+/// with the earlier method in `FibMethod::ALL` first. For a
+/// new implementation to be trusted, this should be empty for
+/// every `n`.
+#[cfg(feature = "std")]
+pub fn find_disagreements(n: usize) -> Vec<(FibMethod, FibMethod)> {
+    let results = fib_all(n);
+    let mut pairs = Vec::new();
+    for (i, &(m, f)) in results.iter().enumerate() {
+        for &(k, g) in &results[i + 1..] {
+            if f != g {
+                pairs.push((m, k));
+            }
+        }
+    }
+    pairs
+}
+
+/// The n-th [Fibonacci word](https://en.wikipedia.org/wiki/Fibonacci_word),
+/// built like the numbers but by concatenation: S(0) = "0",
+/// S(1) = "01", and S(n) = S(n-1) S(n-2). S(n) has F(n + 2)
+/// characters, so this gets big fast.
+#[cfg(feature = "std")]
+pub fn fibonacci_word(n: usize) -> String {
+    let mut x = String::from("0");
+    let mut y = String::from("01");
+    for _ in 0..n {
+        let z = y.clone() + &x;
+        x = std::mem::replace(&mut y, z);
+    }
+    x
+}
+
+/// Like `fib_lookup()`, but the table is built at run time,
+/// the first time it is needed, and then shared by every
+/// thread. `OnceLock` makes sure it is built only once even
+/// if several threads ask at the same moment.
+#[cfg(feature = "std")]
+pub fn fib_cached(n: usize) -> Option<u32> {
+    static TABLE: std::sync::OnceLock<Vec<u32>> = std::sync::OnceLock::new();
+    TABLE.get_or_init(|| fib_iter().collect()).get(n).copied()
+}
+
+/// `fib_lookup()` with its input and output passed through
+/// `black_box()`, for benchmarking. Called with a literal
+/// index, most of the tour's functions are simple enough that
+/// the compiler can work out the answer at compile time, or
+/// notice the answer is never used and skip the call: either
+/// way a benchmark ends up timing an empty loop.
+pub fn fib_black_box(n: usize) -> Option<u32> {
+    core::hint::black_box(fib_lookup(core::hint::black_box(n)))
+}
+
+/// The side lengths of the squares that tile a
+/// [Fibonacci spiral](https://en.wikipedia.org/wiki/Golden_spiral):
+/// the first `count` Fibonacci Numbers from F(1) on, leaving
+/// out F(0) = 0, or all of them that fit in a `u32` if there
+/// are fewer.
+#[cfg(feature = "std")]
+pub fn fib_spiral_boxes(count: usize) -> Vec<u32> {
+    fib_iter().skip(1).take(count).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fib_lookup() {
+        let fibs = [0, 1, 1, 2, 3, 5, 8];
+        for (i, fib) in fibs.into_iter().enumerate() {
+            assert_eq!(Some(fib), fib_lookup(i));
+        }
+        assert_eq!(Some(2_971_215_073), fib_lookup(47));
+        for i in 48..=51 {
+            assert!(fib_lookup(i).is_none());
+        }
+        assert!(fib_lookup(100).is_none());
+    }
+
+    #[test]
+    fn test_fib_registered_boundary() {
+        for i in 40..=47 {
+            assert_eq!(fib_lookup(i), fib_registered(i), "F({})", i);
+        }
+        for i in [48, 49, 50, 51, 100, usize::MAX - 1, usize::MAX] {
+            assert!(fib_registered(i).is_none(), "F({})", i);
+        }
+    }
+
+    #[test]
+    fn test_fib_table() {
+        for (i, &fib) in FIB_TABLE.iter().enumerate() {
+            assert_eq!(fib_array(i), Some(fib));
+        }
+        assert!(fib_array(FIB_TABLE.len()).is_none());
+    }
+
+    #[test]
+    fn test_fib_const() {
+        assert_eq!(13, Fib::<7>::VALUE);
+        assert_eq!(2_971_215_073, Fib::<47>::VALUE);
+        let a = [0u8; Fib::<5>::VALUE as usize];
+        assert_eq!(5, a.len());
+        match 21 {
+            Fib::<8>::VALUE => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_fib_wide() {
+        for i in 0..=47 {
+            assert_eq!(fib_lookup(i).map(u64::from), fib_u64(i));
+            assert_eq!(fib_lookup(i).map(u128::from), fib_u128(i));
+        }
+        assert_eq!(Some(12_200_160_415_121_876_738), fib_u64(93));
+        assert!(fib_u64(94).is_none());
+        assert_eq!(
+            Some(332_825_110_087_067_562_321_196_029_789_634_457_848),
+            fib_u128(186),
+        );
+        assert!(fib_u128(187).is_none());
+    }
+
+    #[test]
+    fn test_fib_lookup_wide() {
+        assert_eq!(fib_overflow_index::<u64>(), FIB_TABLE_U64.len());
+        assert_eq!(fib_overflow_index::<u128>(), FIB_TABLE_U128.len());
+        for i in 0..=200 {
+            assert_eq!(fib_u64(i), fib_lookup_u64(i));
+            assert_eq!(fib_u128(i), fib_lookup_u128(i));
+        }
+    }
+
+    #[test]
+    fn test_fib_iter() {
+        let fibs: Vec<u32> = fib_iter().take_while(|_| true).collect();
+        assert_eq!(48, fibs.len());
+        for (i, fib) in fibs.into_iter().enumerate() {
+            assert_eq!(fib_lookup(i), Some(fib));
+        }
+        for i in 0..=51 {
+            assert_eq!(fib_lookup(i), fib_iter().nth(i));
+        }
+    }
+
+    #[test]
+    fn test_fib_iter_double_ended() {
+        assert_eq!(Some(2_971_215_073), fib_iter().next_back());
+        assert_eq!(48, fib_iter().len());
+        for (i, (f, b)) in fib_iter().zip(fib_iter().rev()).enumerate() {
+            assert_eq!((FIB_TABLE[i], FIB_TABLE[47 - i]), (f, b));
+        }
+        let forward: Vec<u32> = fib_iter().collect();
+        let mut backward: Vec<u32> = fib_iter().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(Some(1), fib_iter().rfind(|&f| f < 2));
+
+        // Take from both ends in turn until they meet.
+        for skip in 0..4 {
+            let mut fibs = fib_iter();
+            for _ in 0..skip {
+                fibs.next();
+            }
+            let mut seen = vec![];
+            while let Some(f) = fibs.next() {
+                seen.push(f);
+                seen.extend(fibs.next_back());
+            }
+            assert!(fibs.next().is_none() && fibs.next_back().is_none());
+            seen.sort();
+            assert_eq!(FIB_TABLE[skip..], seen[..]);
+        }
+    }
+
+    #[test]
+    fn test_fib_fill() {
+        let mut buf = [7; 3];
+        assert_eq!(3, fib_fill(&mut buf));
+        assert_eq!([0, 1, 1], buf);
+
+        let mut buf = [7; 50];
+        assert_eq!(48, fib_fill(&mut buf));
+        assert_eq!(FIB_TABLE[..], buf[..48]);
+        assert_eq!([7, 7], buf[48..]);
+
+        assert_eq!(0, fib_fill(&mut []));
+    }
+
+    #[test]
+    fn test_fib_iter_rev() {
+        assert_eq!(48, fib_iter_rev().count());
+        assert_eq!(Some(2_971_215_073), fib_iter_rev().next());
+        assert_eq!(Some(0), fib_iter_rev().last());
+        let forward: Vec<u32> = fib_iter().collect();
+        assert!(fib_iter_rev().eq(forward.into_iter().rev()));
+    }
+
+    #[test]
+    fn test_fib_signed() {
+        let negafibs = [0, 1, -1, 2, -3, 5, -8, 13, -21];
+        for (i, fib) in negafibs.into_iter().enumerate() {
+            assert_eq!(Some(fib), fib_signed(-(i as i32)));
+            assert_eq!(Some(fib.abs()), fib_signed(i as i32));
+        }
+        assert_eq!(Some(1_836_311_903), fib_signed(46));
+        assert_eq!(Some(-1_836_311_903), fib_signed(-46));
+        assert!(fib_signed(47).is_none());
+        assert!(fib_signed(-47).is_none());
+        assert!(fib_signed(i32::MIN).is_none());
+    }
+
+    #[test]
+    fn test_lucas() {
+        let lucas_numbers = [2, 1, 3, 4, 7, 11, 18, 29];
+        for (i, l) in lucas_numbers.into_iter().enumerate() {
+            assert_eq!(Some(l), lucas(i));
+        }
+        for i in 1..=45 {
+            let f = fib_array(i - 1).unwrap() + fib_array(i + 1).unwrap();
+            assert_eq!(Some(f), lucas(i));
+        }
+        assert_eq!(Some(4_106_118_243), lucas(46));
+        assert!(lucas(47).is_none());
+    }
+
+    #[test]
+    fn test_fib_index() {
+        assert_eq!(Some(0), fib_index(0));
+        assert_eq!(Some(1), fib_index(1));
+        assert_eq!(Some(3), fib_index(2));
+        for i in 3..=47 {
+            assert_eq!(Some(i), fib_index(fib_lookup(i).unwrap()));
+        }
+        for v in [4, 6, 100, u32::MAX] {
+            assert!(fib_index(v).is_none());
+        }
+    }
+
+    #[test]
+    fn test_is_fibonacci() {
+        for v in [0, 1, 2, 3, 5, 8, 13] {
+            assert!(is_fibonacci(v));
+        }
+        for v in [4, 6, 7, 9, 10, 100, u32::MAX] {
+            assert!(!is_fibonacci(v));
+        }
+        for i in 0..=47 {
+            let f = fib_lookup(i).unwrap();
+            assert!(is_fibonacci(f));
+            if f > 3 {
+                assert!(!is_fibonacci(f - 1));
+                assert!(!is_fibonacci(f + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fib_checked() {
+        for i in 0..=47 {
+            assert_eq!(fib_lookup(i), fib_checked(i).ok());
+        }
+        let err = fib_checked(48).unwrap_err();
+        assert_eq!(FibError::Overflow { index: 48 }, err);
+        assert_eq!("F(48) overflows u32", err.to_string());
+    }
+
+    #[test]
+    fn test_fib_saturating() {
+        for i in 0..=47 {
+            assert_eq!(fib_lookup(i), Some(fib_saturating(i)));
+        }
+        for i in [48, 100, usize::MAX] {
+            assert_eq!(u32::MAX, fib_saturating(i));
+        }
+    }
+
+    #[test]
+    fn test_fib_wrapping() {
+        for i in 0..=47 {
+            assert_eq!(fib_lookup(i), Some(fib_wrapping(i)));
+        }
+        assert_eq!(512_559_680, fib_wrapping(48));
+        assert_eq!(3_996_334_433, fib_wrapping(50));
+        let (mut x, mut y) = (0u32, 1u32);
+        for i in 0..=1000 {
+            assert_eq!(x, fib_wrapping(i), "F({})", i);
+            (x, y) = (y, x.wrapping_add(y));
+        }
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_fib_wrapping_huge() {
+        // The Pisano period of 2³² is 3 × 2³¹.
+        let period = 3 << 31;
+        assert_eq!(fib_wrapping(50), fib_wrapping(period + 50));
+        assert_eq!(fib_wrapping(50), fib_wrapping(1000 * period + 50));
+        assert_eq!(1_070_270_178, fib_wrapping(usize::MAX));
+        assert_eq!(
+            Some(1_070_270_178),
+            fib_policy(usize::MAX, OnOverflow::Wrap)
+        );
+        let lucas_wrapping = FibBuilder::new().seeds(2, 1).on_overflow(OnOverflow::Wrap);
+        assert_eq!(Some(123), lucas_wrapping.compute(period + 10));
+        assert_eq!(Some(3_238_583_700), lucas_wrapping.compute(usize::MAX));
+    }
+
+    #[test]
+    fn test_fib_mod() {
+        assert_eq!(55, fib_mod(10, 1000));
+        assert_eq!(875, fib_mod(1_000_000_000_000_000_000, 1000));
+        for m in (1..=100).chain([1000, 65_536]) {
+            for n in 0..=47 {
+                assert_eq!(fib_lookup(n).unwrap() % m, fib_mod(n as u128, m));
+            }
+        }
+        assert_eq!(0, fib_mod(u128::MAX, 1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_cache() {
+        let mut cache = FibCache::new();
+        assert_eq!(&[0, 1], cache.values());
+        let mut len = cache.values().len();
+        for i in [10, 5, 47, 0, 48, 100] {
+            assert_eq!(fib_lookup(i), cache.get(i));
+            assert!(cache.values().len() >= len);
+            len = cache.values().len();
+        }
+        assert_eq!(48, len);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_binet_exact() {
+        for i in 0..=47 {
+            assert_eq!(fib_closed(i), fib_binet_exact(i));
+            assert_eq!(fib_lookup(i), fib_binet_exact(i));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_vec_fancy() {
+        assert_eq!(Some(0), fib_vec_fancy(0));
+        assert_eq!(Some(1), fib_vec_fancy(1));
+        assert_eq!(Some(1), fib_vec_fancy(2));
+        assert!(fib_vec_fancy(usize::MAX).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_closed() {
+        for i in 0..=47 {
+            assert_eq!(fib_lookup(i), fib_closed(i));
+        }
+        assert_eq!(Some(2_971_215_073), fib_closed(47));
+        assert!(fib_closed(48).is_none());
+        assert!(fib_closed(usize::MAX).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_range() {
+        let fibs = [0, 1, 1, 2, 3, 5, 8, 13].map(Some);
+        assert_eq!(fibs.to_vec(), fib_range(0, 7));
+        assert_eq!(fibs[3..].to_vec(), fib_range(3, 7));
+        assert_eq!(vec![fib_lookup(47), None, None], fib_range(47, 49));
+        assert_eq!(vec![None], fib_range(100, 100));
+        assert!(fib_range(5, 4).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_zeckendorf() {
+        assert_eq!(vec![89, 8, 3], zeckendorf(100));
+        assert!(zeckendorf(0).is_empty());
+        for v in (0..=10_000).chain([2_971_215_073, u32::MAX]) {
+            let terms = zeckendorf(v);
+            assert_eq!(u64::from(v), terms.iter().map(|&f| u64::from(f)).sum());
+            let indices: Vec<usize> = terms.iter().map(|&f| fib_index(f).unwrap()).collect();
+            for w in indices.windows(2) {
+                assert!(w[0] > w[1] + 1);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_coding() {
+        let bits = |s: &str| s.chars().map(|c| c == '1').collect::<Vec<_>>();
+        assert_eq!(bits("0100100011"), fib_encode(65));
+        assert_eq!(bits("11"), fib_encode(1));
+        assert!(fib_encode(0).is_empty());
+        for v in (1..=1000).chain([2_971_215_073, u32::MAX]) {
+            let code = fib_encode(v);
+            assert!(code.ends_with(&[true, true]));
+            assert_eq!(Some(v), fib_decode(&code));
+        }
+        for bad in ["", "1", "0", "10", "111", "1101", "110", "0110011"] {
+            assert!(fib_decode(&bits(bad)).is_none(), "{:?}", bad);
+        }
+        let too_big = bits(&format!("{}11", "0".repeat(46)));
+        assert!(fib_decode(&too_big).is_none());
+    }
+
+    #[test]
+    fn test_fib_sum() {
+        assert_eq!(Some(12), fib_sum(5));
+        let mut total = 0u32;
+        for i in 0..=45 {
+            total += fib_lookup(i).unwrap();
+            assert_eq!(Some(total), fib_sum(i));
+        }
+        assert!(total.checked_add(fib_lookup(46).unwrap()).is_none());
+        assert!(fib_sum(46).is_none());
+        assert!(fib_sum(usize::MAX).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_kbonacci() {
+        for i in 0..=51 {
+            assert_eq!(fib_array(i), kbonacci(2, i));
+        }
+        let tribonacci = [0, 0, 1, 1, 2, 4, 7, 13, 24, 44, 81];
+        for (i, t) in tribonacci.into_iter().enumerate() {
+            assert_eq!(Some(t), kbonacci(3, i));
+        }
+        let tetranacci = [0, 0, 0, 1, 1, 2, 4, 8, 15, 29, 56];
+        for (i, t) in tetranacci.into_iter().enumerate() {
+            assert_eq!(Some(t), kbonacci(4, i));
+        }
+        assert_eq!(Some(1), kbonacci(1, 100));
+        assert_eq!(Some(1), kbonacci(1, 0));
+        assert_eq!(Some(1), kbonacci(1, usize::MAX));
+        assert!(kbonacci(3, 100).is_none());
+
+        let k = usize::MAX;
+        assert_eq!(Some(0), kbonacci(k, k - 2));
+        assert_eq!(Some(1), kbonacci(k, k - 1));
+        assert_eq!(Some(1), kbonacci(k, k));
+        let k = 1 << 30;
+        assert_eq!(Some(2), kbonacci(k, k + 1));
+        assert_eq!(Some(1 << 31), kbonacci(k, k + 31));
+        assert_eq!(None, kbonacci(k, k + 32));
+        assert_eq!(None, kbonacci(k, 1 << 31));
+        // The window and the shortcut agree either side of the cutoff.
+        for n in 32..=70 {
+            assert_eq!(kbonacci(33, n), kbonacci(34, n + 1), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_fib_sequence() {
+        assert_eq!(88u32, fib_sequence().take(10).sum());
+        assert_eq!(48, fib_sequence().count());
+        let mut fibs = fib_sequence().skip(47);
+        assert_eq!(fib_lookup(47), fibs.next());
+        assert!(fibs.next().is_none());
+        assert!(fibs.next().is_none());
+    }
+
+    #[test]
+    fn test_fib_pair() {
+        assert_eq!(Some((0, 1)), fib_pair(0));
+        assert_eq!(Some((701_408_733, 1_134_903_170)), fib_pair(44));
+        for i in 0..=46 {
+            assert_eq!(
+                Some((fib_array(i).unwrap(), fib_array(i + 1).unwrap())),
+                fib_pair(i)
+            );
+        }
+        assert!(fib_pair(47).is_none());
+        assert!(fib_pair(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn test_fib_ratio() {
+        const PHI: f64 = 1.618_033_988_749_895;
+        assert!(fib_ratio(0).is_none());
+        assert_eq!(Some(1.0), fib_ratio(1));
+        let mut error = f64::INFINITY;
+        for i in 1..=30 {
+            let e = (fib_ratio(i).unwrap() - PHI).abs();
+            assert!(e < error);
+            error = e;
+        }
+        assert!((fib_ratio(46).unwrap() - PHI).abs() < 1e-9);
+        assert!(fib_ratio(47).is_none());
+    }
+
+    #[test]
+    fn test_fib_number() {
+        let f10 = FibNumber::new(10).unwrap();
+        assert_eq!(
+            FibNumber {
+                index: 10,
+                value: 55
+            },
+            f10
+        );
+        assert_eq!("F(10) = 55", f10.to_string());
+        assert!(FibNumber::new(48).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_number_grouped() {
+        let grouped = |n| FibNumber::new(n).unwrap().grouped();
+        assert_eq!("2,971,215,073", grouped(47));
+        assert_eq!("832,040", grouped(30));
+        assert_eq!("6,765", grouped(20));
+        assert_eq!("144", grouped(12));
+        assert_eq!("0", grouped(0));
+    }
+
+    #[test]
+    fn test_nearest_fibonacci() {
+        assert_eq!(89, nearest_fibonacci(100));
+        assert_eq!(3, nearest_fibonacci(4));
+        assert_eq!(8, nearest_fibonacci(7));
+        assert_eq!(13, nearest_fibonacci(11));
+        assert_eq!(8, nearest_fibonacci(10));
+        assert_eq!(2_971_215_073, nearest_fibonacci(u32::MAX));
+        for i in 0..=47 {
+            let f = fib_lookup(i).unwrap();
+            assert_eq!(f, nearest_fibonacci(f));
+        }
+    }
+
+    #[test]
+    fn test_fib_gcd() {
+        for m in 0..=47 {
+            for n in 0..=47 {
+                let fm = fib_array(m).unwrap() as usize;
+                let fn_ = fib_array(n).unwrap() as usize;
+                assert_eq!(Some(gcd(fm, fn_) as u32), fib_gcd(m, n));
+            }
+        }
+        assert_eq!(Some(8), fib_gcd(600, 1002));
+        assert!(fib_gcd(96, 144).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_decimal_string() {
+        assert_eq!("0", fib_decimal_string(0));
+        assert_eq!("55", fib_decimal_string(10));
+        assert_eq!("354224848179261915075", fib_decimal_string(100));
+        assert_eq!(
+            "139423224561697880139724382870407283950070256587697307264108962948325571622863290691557658876222521294125",
+            fib_decimal_string(500),
+        );
+        for i in 0..=186 {
+            assert_eq!(fib_u128(i).unwrap().to_string(), fib_decimal_string(i));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_fib_sequence() {
+        let mut buf = Vec::new();
+        write_fib_sequence(&mut buf, 4).unwrap();
+        assert_eq!(b"0\n1\n1\n2\n", buf.as_slice());
+
+        let mut buf = Vec::new();
+        write_fib_sequence(&mut buf, 100).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert_eq!(48, text.lines().count());
+        assert!(text.ends_with("2971215073\n"));
+
+        let mut full = [0u8; 5];
+        assert!(write_fib_sequence(&mut full.as_mut_slice(), 10).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_sequence_validation() {
+        let seq = FibSequence::new();
+        assert_eq!(48, seq.0.len());
+        assert_eq!(Ok(seq.clone()), FibSequence::try_from(seq.0.clone()));
+        assert_eq!(Ok(FibSequence(vec![])), FibSequence::try_from(vec![]));
+
+        let mut tampered = seq.0.clone();
+        tampered[20] += 1;
+        let err = FibSequence::try_from(tampered).unwrap_err();
+        assert_eq!(FibError::NotFibonacci { index: 20 }, err);
+        assert_eq!("entry 20 is not F(20)", err.to_string());
+
+        let mut too_long = seq.0;
+        too_long.push(0);
+        assert!(FibSequence::try_from(too_long).is_err());
+        assert!(FibSequence::try_from(vec![1, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_even_fib_sum_below() {
+        assert_eq!(0, even_fib_sum_below(0));
+        assert_eq!(0, even_fib_sum_below(1));
+        assert_eq!(0, even_fib_sum_below(2));
+        assert_eq!(2, even_fib_sum_below(3));
+        assert_eq!(44, even_fib_sum_below(100));
+        assert_eq!(4_613_732, even_fib_sum_below(4_000_000));
+        let evens = (0..=47).filter_map(fib_lookup).filter(|f| f % 2 == 0);
+        assert_eq!(evens.sum::<u32>(), even_fib_sum_below(u32::MAX));
+    }
+
+    #[test]
+    fn test_pisano_period() {
+        let periods = [
+            (1, 1),
+            (2, 3),
+            (3, 8),
+            (4, 6),
+            (5, 20),
+            (10, 60),
+            (1000, 1500),
+        ];
+        for (m, period) in periods {
+            assert_eq!(period, pisano_period(m));
+        }
+    }
+
+    #[test]
+    fn test_fib_enumerate() {
+        assert_eq!(Some((4, 3)), fib_enumerate().nth(4));
+        assert_eq!(48, fib_enumerate().count());
+        for (i, f) in fib_enumerate() {
+            assert_eq!(fib_lookup(i), Some(f));
+        }
+    }
+
+    #[test]
+    fn test_fib_verified() {
+        for i in 0..=51 {
+            assert_eq!(fib_lookup(i), fib_verified(i, fib_doubling));
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "F(3) mismatch")]
+    fn test_fib_verified_mismatch() {
+        fib_verified(3, |n| Some(n as u32));
+    }
+
+    #[test]
+    fn test_gibonacci() {
+        for i in 0..=51 {
+            assert_eq!(fib_array(i), gibonacci(0, 1, i));
+            assert_eq!(lucas(i), gibonacci(2, 1, i));
+        }
+        let seeded = [3, 7, 10, 17, 27, 44];
+        for (i, g) in seeded.into_iter().enumerate() {
+            assert_eq!(Some(g), gibonacci(3, 7, i));
+        }
+        assert_eq!(Some(u32::MAX), gibonacci(u32::MAX, 0, 0));
+        assert!(gibonacci(u32::MAX, 1, 2).is_none());
+    }
+
+    #[test]
+    fn test_fib_count_in_range() {
+        assert_eq!(6, fib_count_in_range(0, 10));
+        assert_eq!(1, fib_count_in_range(1, 1));
+        assert_eq!(0, fib_count_in_range(4, 4));
+        assert_eq!(0, fib_count_in_range(10, 0));
+        assert_eq!(2, fib_count_in_range(1_836_311_903, u32::MAX));
+        assert_eq!(47, fib_count_in_range(0, u32::MAX));
+    }
+
+    #[test]
+    fn test_max_representable_index() {
+        let n = max_representable_index();
+        assert_eq!(47, n);
+        assert!(fib_array(n).is_some());
+        assert!(fib_array(n + 1).is_none());
+    }
+
+    #[test]
+    fn test_fib_naive() {
+        // Keep this small: fib_naive() is exponential.
+        for i in 0..=30 {
+            assert_eq!(fib_lookup(i), fib_naive(i));
+        }
+    }
+
+    #[test]
+    fn test_fib_digit_count() {
+        assert_eq!(1, fib_digit_count(0));
+        assert_eq!(2, fib_digit_count(10));
+        assert_eq!(21, fib_digit_count(100));
+        assert_eq!(209, fib_digit_count(1000));
+        for i in 0..=47 {
+            assert_eq!(fib_lookup(i).unwrap().to_string().len(), fib_digit_count(i));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_digit_count_large() {
+        for i in 0..=2000 {
+            assert_eq!(fib_decimal_string(i).len(), fib_digit_count(i), "F({})", i);
+        }
+    }
+
+    #[test]
+    fn test_fib_digit_count_huge() {
+        // Computed independently, from log₁₀ F(n) to 200
+        // significant digits.
+        let expected: [(u64, u64); 7] = [
+            (19_032_185_831_821, 3_977_491_605_791),
+            ((1 << 48) + 12_345, 58_824_791_174_758),
+            ((1 << 56) + 999, 15_059_146_540_077_705),
+            ((1 << 60) + 7, 240_946_344_641_239_937),
+            (1_000_000_000_000_000_000, 208_987_640_249_978_734),
+            (u64::MAX - 1000, 3_855_141_514_259_838_754),
+            (u64::MAX, 3_855_141_514_259_838_963),
+        ];
+        for (n, digits) in expected {
+            let (Ok(n), Ok(digits)) = (usize::try_from(n), usize::try_from(digits)) else {
+                continue;
+            };
+            assert_eq!(digits, fib_digit_count(n), "F({})", n);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_even_fibs() {
+        assert_eq!(vec![2, 8, 34], even_fibs(3));
+        assert!(even_fibs(0).is_empty());
+        let all = even_fibs(100);
+        let expected: Vec<u32> = (1..=15).map(|k| fib_lookup(3 * k).unwrap()).collect();
+        assert_eq!(expected, all);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_lucas_merged() {
+        let expected = [0, 1, 2, 3, 4, 5, 7, 8, 11, 13, 18, 21, 29, 34, 47, 55];
+        assert_eq!(expected.to_vec(), fib_lucas_merged(expected.len()));
+        let all = fib_lucas_merged(usize::MAX);
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+        // 1, 2 and 3 are in both sequences.
+        assert_eq!(47 + 47 - 3, all.len());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_index_approx() {
+        assert_eq!(10, fib_index_approx(55).round() as usize);
+        for i in 2..=47 {
+            let f = fib_lookup(i).unwrap();
+            assert_eq!(i, fib_index_approx(f).round() as usize);
+        }
+        let mut prev = fib_index_approx(0);
+        for v in (1..10_000).chain([u32::MAX]) {
+            let estimate = fib_index_approx(v);
+            assert!(estimate > prev);
+            prev = estimate;
+        }
+    }
+
+    #[test]
+    fn test_fib_index_type() {
+        let i = FibIndex::try_from(47).unwrap();
+        assert_eq!(47, i.index());
+        assert_eq!(2_971_215_073, i.value());
+        assert_eq!(0, FibIndex::try_from(0).unwrap().value());
+        assert_eq!(
+            Err(FibError::Overflow { index: 48 }),
+            FibIndex::try_from(48),
+        );
+    }
+
+    #[test]
+    fn test_fib_sum_of_squares() {
+        assert_eq!(Some(15), fib_sum_of_squares(4));
+        let mut total = 0u64;
+        for i in 0..=46 {
+            let f = u64::from(fib_lookup(i).unwrap());
+            total += f * f;
+            assert_eq!(u32::try_from(total).ok(), fib_sum_of_squares(i));
+        }
+        assert!(fib_sum_of_squares(47).is_none());
+    }
+
+    #[test]
+    fn test_euclid_steps() {
+        let f = |n| fib_lookup(n).unwrap();
+        assert_eq!(9, euclid_steps(f(10), f(11)));
+        assert_eq!(9, euclid_steps(f(11), f(10)));
+        assert_eq!(0, euclid_steps(7, 0));
+        for steps in 0..=45 {
+            let (a, b) = worst_case_euclid_inputs(steps).unwrap();
+            assert_eq!(steps, euclid_steps(a, b));
+        }
+        assert!(worst_case_euclid_inputs(46).is_none());
+        // Nothing smaller takes as many steps.
+        for b in 1..=200 {
+            let most = (1..).take_while(|&k| f(k + 2) <= b).last().unwrap_or(0);
+            for a in 0..=b {
+                assert!(euclid_steps(a, b) <= most.max(1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fib_is_even() {
+        for i in 0..=47 {
+            assert_eq!(fib_lookup(i).unwrap() & 1 == 0, fib_is_even(i));
+        }
+        assert!(fib_is_even(99));
+        assert!(!fib_is_even(usize::MAX - 1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_table_string() {
+        let table = fib_table_string(5);
+        let mut lines = table.lines();
+        assert_eq!(Some("n | F(n)"), lines.next());
+        assert_eq!(Some("0 |    0"), lines.next());
+        assert_eq!(6, table.lines().count());
+        assert_eq!(49, fib_table_string(100).lines().count());
+        assert!(fib_table_string(100).ends_with("47 | 2971215073\n"));
+        assert_eq!("n | F(n)\n", fib_table_string(0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_closed_u64() {
+        for i in 0..=CLOSED_FORM_MAX_INDEX {
+            assert_eq!(fib_u64(i), fib_closed_u64(i), "F({})", i);
+        }
+        assert_eq!(Some(72_723_460_248_141), fib_closed_u64(68));
+        assert_eq!(None, fib_closed_u64(69));
+        assert_eq!(None, fib_closed_u64(usize::MAX));
+    }
+
+    #[test]
+    fn test_fib_with() {
+        for &method in FibMethod::ALL {
+            for i in 0..=47 {
+                assert_eq!(fib_lookup(i), fib_with(method, i), "{:?}: F({})", method, i);
+            }
+            assert_eq!(None, fib_with(method, 48), "{:?}", method);
+            assert_eq!(Some(method), FibMethod::from_name(method.name()));
+        }
+        assert_eq!(None, FibMethod::from_name("naive"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_all() {
+        for i in 0..=51 {
+            let results = fib_all(i);
+            assert_eq!(FibMethod::ALL.len(), results.len());
+            let (_, first) = results[0];
+            for (method, result) in results {
+                assert_eq!(first, result, "{:?}: F({})", method, i);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fibonacci_word() {
+        assert_eq!("0", fibonacci_word(0));
+        assert_eq!("01", fibonacci_word(1));
+        assert_eq!("010", fibonacci_word(2));
+        assert_eq!("01001010", fibonacci_word(4));
+        for i in 0..20 {
+            let word = fibonacci_word(i);
+            assert_eq!(fib_lookup(i + 2), Some(word.len() as u32), "S({})", i);
+            assert!(fibonacci_word(i + 1).starts_with(&word), "S({})", i);
+        }
+    }
+
+    #[test]
+    fn test_greatest_fib_le() {
+        assert_eq!((0, 0), greatest_fib_le(0));
+        assert_eq!((2, 1), greatest_fib_le(1));
+        assert_eq!((4, 3), greatest_fib_le(4));
+        assert_eq!((11, 89), greatest_fib_le(100));
+        assert_eq!((11, 89), greatest_fib_le(89));
+        assert_eq!((12, 144), greatest_fib_le(144));
+        assert_eq!((47, 2_971_215_073), greatest_fib_le(u32::MAX));
+        for (i, &f) in FIB_TABLE.iter().enumerate().skip(3) {
+            assert_eq!((i, f), greatest_fib_le(f));
+            assert_eq!((i - 1, FIB_TABLE[i - 1]), greatest_fib_le(f - 1));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_cached() {
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for i in 0..=60 {
+                        assert_eq!(fib_lookup(i), fib_cached(i), "F({})", i);
+                    }
+                });
+            }
+        });
+        assert_eq!(None, fib_cached(usize::MAX));
+    }
+
+    #[test]
+    fn test_fib_black_box() {
+        for i in 0..=50 {
+            assert_eq!(fib_lookup(i), fib_black_box(i));
+        }
+    }
+
+    #[test]
+    fn test_fib_mod_bigindex() {
+        let n = "1234567890123456789012345678901234567890";
+        // n mod 60 = 30, and F(30) = 832040.
+        assert_eq!(Ok(0), fib_mod_bigindex(n, 10));
+        // n mod 1500 = 390.
+        assert_eq!(Ok(fib_mod(390, 1000)), fib_mod_bigindex(n, 1000));
+        let max = u128::MAX.to_string();
+        assert_eq!(Ok(fib_mod(u128::MAX, 97)), fib_mod_bigindex(&max, 97));
+        assert_eq!(Ok(0), fib_mod_bigindex("0", 7));
+        assert_eq!(Err(ParseError::Empty), fib_mod_bigindex("", 7));
+        let e = fib_mod_bigindex("12x4", 7).unwrap_err();
+        assert_eq!(ParseError::InvalidDigit { position: 2 }, e);
+        assert_eq!(
+            Err(ParseError::InvalidDigit { position: 0 }),
+            fib_mod_bigindex("-1", 7)
+        );
+    }
+
+    #[test]
+    fn test_lucas_fib_ratio() {
+        const SQRT5: f64 = 2.236_067_977_499_79;
+        assert!(lucas_fib_ratio(0).is_none());
+        assert_eq!(Some(1.0), lucas_fib_ratio(1));
+        assert_eq!(Some(3.0), lucas_fib_ratio(2));
+        let mut error = f64::INFINITY;
+        for i in 1..=20 {
+            let e = (lucas_fib_ratio(i).unwrap() - SQRT5).abs();
+            assert!(e < error);
+            error = e;
+        }
+        assert!((lucas_fib_ratio(46).unwrap() - SQRT5).abs() < 1e-9);
+        assert!(lucas_fib_ratio(47).is_none());
+    }
+
+    #[test]
+    fn test_validate_fib_prefix() {
+        assert!(validate_fib_prefix(&[]));
+        assert!(validate_fib_prefix(&[0]));
+        assert!(validate_fib_prefix(&[0, 1, 1, 2]));
+        assert!(validate_fib_prefix(&FIB_TABLE));
+        assert!(!validate_fib_prefix(&[1]));
+        assert!(!validate_fib_prefix(&[0, 1, 1, 2, 4, 5, 8]));
+        assert!(!validate_fib_prefix(&[1, 1, 2, 3, 5]));
+        let mut too_long = FIB_TABLE.to_vec();
+        too_long.push(FIB_TABLE[46].wrapping_add(FIB_TABLE[47]));
+        assert!(!validate_fib_prefix(&too_long));
+    }
+
+    #[test]
+    fn test_fib_double_index() {
+        assert_eq!(Some(0), fib_double_index(0));
+        for i in 0..=23 {
+            assert_eq!(fib_array(2 * i), fib_double_index(i), "F(2 * {})", i);
+        }
+        assert_eq!(None, fib_double_index(24));
+        assert_eq!(None, fib_double_index(usize::MAX));
+    }
+
+    #[test]
+    fn test_fib_from_str() {
+        assert_eq!(Ok(55), fib_from_str("10"));
+        assert_eq!(Ok(0), fib_from_str("0"));
+        let invalid = |position| Err(FibError::Parse(ParseError::InvalidDigit { position }));
+        assert_eq!(invalid(0), fib_from_str("-1"));
+        assert_eq!(invalid(0), fib_from_str("abc"));
+        assert_eq!(invalid(0), fib_from_str("+1"));
+        assert_eq!(invalid(2), fib_from_str("10 "));
+        assert_eq!(Err(FibError::Parse(ParseError::Empty)), fib_from_str(""));
+        assert_eq!(Err(FibError::Overflow { index: 48 }), fib_from_str("48"));
+        let too_large = "1".repeat(40);
+        assert_eq!(
+            Err(FibError::Parse(ParseError::TooLarge)),
+            fib_from_str(&too_large)
+        );
+        assert_eq!(Ok(usize::MAX), parse_index(&usize::MAX.to_string()));
+        assert_eq!(
+            "bad index: invalid digit at position 0",
+            fib_from_str("x").unwrap_err().to_string()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_first_fib_with_digits() {
+        assert_eq!(0, first_fib_with_digits(0));
+        assert_eq!(0, first_fib_with_digits(1));
+        assert_eq!(7, first_fib_with_digits(2));
+        assert_eq!(12, first_fib_with_digits(3));
+        assert_eq!(4782, first_fib_with_digits(1000));
+        for k in 2..=100 {
+            let n = first_fib_with_digits(k);
+            assert!(fib_decimal_string(n).len() >= k, "k = {}", k);
+            assert!(fib_decimal_string(n - 1).len() < k, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn test_fib_policy() {
+        let policies = [OnOverflow::None, OnOverflow::Saturate, OnOverflow::Wrap];
+        for i in 0..=47 {
+            for policy in policies {
+                assert_eq!(
+                    fib_lookup(i),
+                    fib_policy(i, policy),
+                    "{:?}: F({})",
+                    policy,
+                    i
+                );
+            }
+        }
+        assert_eq!(None, fib_policy(48, OnOverflow::None));
+        assert_eq!(Some(u32::MAX), fib_policy(48, OnOverflow::Saturate));
+        let f48 = 4_807_526_976u64;
+        assert_eq!(
+            Some((f48 - (1 << 32)) as u32),
+            fib_policy(48, OnOverflow::Wrap)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_code_length() {
+        assert_eq!(0, fib_code_length(0));
+        assert_eq!(2, fib_code_length(1));
+        for value in (1..=1000).chain([u32::MAX]) {
+            assert_eq!(fib_encode(value).len(), fib_code_length(value), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_fib_from_end() {
+        assert_eq!(Some(2_971_215_073), fib_from_end(0));
+        assert_eq!(Some(1_836_311_903), fib_from_end(1));
+        assert_eq!(Some(0), fib_from_end(47));
+        assert_eq!(None, fib_from_end(48));
+        assert!(fib_iter_rev().eq((0..48).map_while(fib_from_end)));
+    }
+
+    #[test]
+    fn test_reciprocal_fib_sum() {
+        assert_eq!(0.0, reciprocal_fib_sum(0));
+        let hand = 1.0 + 1.0 + 1.0 / 2.0 + 1.0 / 3.0 + 1.0 / 5.0;
+        assert!((reciprocal_fib_sum(5) - hand).abs() < 1e-9);
+        for i in 1..=40 {
+            assert!(reciprocal_fib_sum(i) > reciprocal_fib_sum(i - 1));
+        }
+        assert!((reciprocal_fib_sum(100) - 3.359_885_666_2).abs() < 1e-9);
+        assert_eq!(reciprocal_fib_sum(100), reciprocal_fib_sum(10_000));
+    }
+
+    #[test]
+    fn test_fib_builder() {
+        assert_eq!(fib_array(10), FibBuilder::new().compute(10));
+        assert_eq!(FibBuilder::new(), FibBuilder::default());
+        let lucases = FibBuilder::new().seeds(2, 1);
+        for i in 0..=50 {
+            assert_eq!(lucas(i), lucases.compute(i), "L({})", i);
+        }
+        let saturating = FibBuilder::new().on_overflow(OnOverflow::Saturate);
+        assert_eq!(Some(2_971_215_073), saturating.compute(47));
+        assert_eq!(Some(u32::MAX), saturating.compute(48));
+        let wrapping = FibBuilder::new().on_overflow(OnOverflow::Wrap);
+        for i in 0..=100 {
+            assert_eq!(Some(fib_wrapping(i)), wrapping.compute(i));
+        }
+        for (a, b) in [(2, 1), (7, 3), (u32::MAX, u32::MAX - 1)] {
+            let seeded = wrapping.seeds(a, b);
+            let (mut x, mut y) = (a, b);
+            for i in 0..=1000 {
+                assert_eq!(Some(x), seeded.compute(i), "G({})", i);
+                (x, y) = (y, x.wrapping_add(y));
+            }
+        }
+        let lucas_saturating = lucases.on_overflow(OnOverflow::Saturate);
+        assert_eq!(Some(u32::MAX), lucas_saturating.compute(47));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_find_disagreements() {
+        for i in 0..=100 {
+            assert_eq!(
+                Vec::<(FibMethod, FibMethod)>::new(),
+                find_disagreements(i),
+                "F({})",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_checked_u32_sum() {
+        let total: CheckedU32 = fib_iter().map(CheckedU32::some).sum();
+        assert_eq!(CheckedU32(None), total);
+        let partial: CheckedU32 = fib_iter().take(10).map(CheckedU32::some).sum();
+        assert_eq!(CheckedU32(Some(88)), partial);
+        assert_eq!(
+            CheckedU32(fib_sum(40)),
+            fib_iter().take(41).map(CheckedU32::some).sum()
+        );
+        let poisoned = [CheckedU32::some(1), CheckedU32(None), CheckedU32::some(2)];
+        assert_eq!(CheckedU32(None), poisoned.into_iter().sum());
+        assert_eq!(CheckedU32(Some(0)), core::iter::empty().sum());
+    }
+
+    #[test]
+    fn test_gcd_of_fibs() {
+        for a in 0..=47 {
+            for b in 0..=47 {
+                assert_eq!(fib_gcd(a, b), gcd_of_fibs(a, b), "gcd(F({}), F({}))", a, b);
+            }
+        }
+        assert_eq!(Some(3), gcd_of_fibs(96, 100));
+        assert_eq!(Some(55), gcd_of_fibs(10, 1000));
+        assert_eq!(None, gcd_of_fibs(1000, 1500));
+    }
+
+    #[test]
+    fn test_golden_next() {
+        assert_eq!(Some(13), golden_next(8));
+        assert_eq!(Some(1), golden_next(0));
+        assert_eq!(Some(2), golden_next(1));
+        assert_eq!(None, golden_next(9));
+        assert_eq!(None, golden_next(2_971_215_073));
+        for (i, &f) in FIB_TABLE.iter().enumerate().skip(2) {
+            assert_eq!(fib_lookup(i + 1), golden_next(f), "F({})", i);
+        }
+    }
+
+    #[test]
+    fn test_fib_state() {
+        assert_eq!(Some((0, 1)), fib_state(0));
+        assert_eq!(Some((1, 0)), fib_state(1));
+        assert_eq!(Some((2_971_215_073, 1_836_311_903)), fib_state(47));
+        assert_eq!(None, fib_state(48));
+        let prevs = core::iter::once(1).chain(fib_sequence());
+        for (i, state) in fib_sequence().zip(prevs).enumerate() {
+            assert_eq!(Some(state), fib_state(i), "F({})", i);
+            assert_eq!(fib_fold(i), fib_state(i).map(|(x, _)| x));
+            assert_eq!(fib_lazy(i), fib_state(i).map(|(x, _)| x));
+        }
+        for i in 1..=47 {
+            let (x, y) = fib_pair(i - 1).unwrap();
+            assert_eq!(Some((y, x)), fib_state(i));
+        }
+    }
+
+    #[test]
+    fn test_fib_digital_root() {
+        fn digital_root(mut x: u32) -> u32 {
+            while x >= 10 {
+                let mut sum = 0;
+                while x > 0 {
+                    sum += x % 10;
+                    x /= 10;
+                }
+                x = sum;
+            }
+            x
+        }
+        for (i, &f) in FIB_TABLE.iter().enumerate() {
+            assert_eq!(digital_root(f), fib_digital_root(i), "F({})", i);
+        }
+        for i in 1..1000 {
+            assert_eq!(fib_digital_root(i), fib_digital_root(i + 24));
+        }
+        assert_eq!(9, fib_digital_root(24));
+        assert_eq!(fib_digital_root(24), fib_digital_root(48));
+        assert_eq!(0, fib_digital_root(0));
+        assert_eq!(
+            fib_digital_root(usize::MAX % 24),
+            fib_digital_root(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn test_fib_div_consecutive() {
+        assert_eq!(None, fib_div_consecutive(0));
+        assert_eq!(Some((1, 0)), fib_div_consecutive(1));
+        assert_eq!(Some((2, 0)), fib_div_consecutive(2));
+        for i in 3..=46 {
+            assert_eq!(
+                Some((1, FIB_TABLE[i - 1])),
+                fib_div_consecutive(i),
+                "F({})",
+                i
+            );
+        }
+        assert_eq!(None, fib_div_consecutive(47));
+    }
+
+    #[test]
+    fn test_fib_const_array() {
+        const TABLE: [u32; 10] = fib_const_array();
+        assert_eq!(FIB_TABLE[..10], TABLE);
+        assert_eq!([0, 1, 1, 2, 3, 5, 8, 13], fib_const_array::<8>());
+        assert_eq!([0u32; 0], fib_const_array::<0>());
+        assert_eq!(FIB_TABLE, fib_const_array::<48>());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_with_callback() {
+        for n in [0, 1, 10, 47] {
+            let mut steps = vec![];
+            let f = fib_with_callback(n, |i, f| steps.push((i, f)));
+            assert_eq!(fib_lookup(n), f);
+            let expected: Vec<(usize, u32)> = fib_range(0, n)
+                .into_iter()
+                .map(Option::unwrap)
+                .enumerate()
+                .collect();
+            assert_eq!(expected, steps);
+        }
+        let mut count = 0;
+        assert_eq!(None, fib_with_callback(100, |_, _| count += 1));
+        assert_eq!(48, count);
+    }
+
+    #[test]
+    fn test_golden_convergent() {
+        assert_eq!(None, golden_convergent(0));
+        assert_eq!(Some((1, 1)), golden_convergent(1));
+        assert_eq!(Some((8, 5)), golden_convergent(5));
+        for i in 1..=46 {
+            let (p, q) = golden_convergent(i).unwrap();
+            assert_eq!(1, gcd(p as usize, q as usize), "{}/{}", p, q);
+            assert_eq!(fib_ratio(i), Some(f64::from(p) / f64::from(q)));
+        }
+        assert_eq!(None, golden_convergent(47));
+    }
+
+    #[test]
+    fn test_fib_query() {
+        for s in ["F(10)", "fib(10)", "fib 10", "10"] {
+            let query: FibQuery = s.parse().unwrap();
+            assert_eq!(FibQuery { index: 10 }, query, "{}", s);
+            assert_eq!(Some(55), query.evaluate());
+        }
+        assert_eq!(None, "F(48)".parse::<FibQuery>().unwrap().evaluate());
+        assert_eq!(Err(ParseError::Unclosed), "F(10".parse::<FibQuery>());
+        assert_eq!(Err(ParseError::Empty), "fib()".parse::<FibQuery>());
+        let invalid = |position| Err(ParseError::InvalidDigit { position });
+        assert_eq!(invalid(3), "F(1x)".parse::<FibQuery>());
+        assert_eq!(invalid(0), "G(10)".parse::<FibQuery>());
+        assert_eq!(invalid(4), "fib  10".parse::<FibQuery>());
+        assert_eq!(invalid(0), "F 10".parse::<FibQuery>());
+        assert_eq!(invalid(0), "f(10)".parse::<FibQuery>());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_closed_error() {
+        assert_eq!(0.0, fib_closed_error(0));
+        for i in 0..=47 {
+            assert!(fib_closed_error(i) < 0.5, "F({})", i);
+        }
+        assert!(fib_closed_error(47) < 1e-4);
+        assert!(fib_closed_error(47) > fib_closed_error(20));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_fib_closed_error_overflow() {
+        fib_closed_error(48);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_spiral_boxes() {
+        assert_eq!(vec![1, 1, 2, 3, 5], fib_spiral_boxes(5));
+        assert!(fib_spiral_boxes(0).is_empty());
+        let boxes = fib_spiral_boxes(usize::MAX);
+        assert_eq!(47, boxes.len());
+        assert!(!boxes.contains(&0));
+        assert_eq!(FIB_TABLE[1..], boxes[..]);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_first_fib_with_digits_huge() {
+        // Computed independently, by binary search on log₁₀ F(n)
+        // to 200 significant digits. F(usize::MAX) has
+        // 3855141514259838963 digits.
+        let expected = [
+            (3_977_491_605_791, 19_032_185_831_817),
+            (1_000_000_000_000_000_000, 4_784_971_966_781_665_969),
+            (3_855_141_514_259_838_963, 18_446_744_073_709_551_611),
+        ];
+        for (k, n) in expected {
+            assert_eq!(n, first_fib_with_digits(k), "k = {}", k);
+        }
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    #[should_panic]
+    fn test_first_fib_with_digits_past_usize() {
+        first_fib_with_digits(3_855_141_514_259_838_964);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_first_fib_with_digits_too_many() {
+        first_fib_with_digits(usize::MAX);
+    }
+
+    fn test_fib(name: &str, f: fn(usize) -> Option<u32>) {
+        for i in 0..=47 {
+            let fi = f(i);
+            let fli = fib_lookup(i);
+            eprintln!("{}: {} {:?} ({:?})", name, i, fi, fli);
+            assert_eq!(fi, fli);
+        }
+        for i in 48..=51 {
+            let fi = f(i);
+            eprintln!("{}: {} {:?} (None)", name, i, fi);
+            assert!(fi.is_none());
+        }
+    }
+
+    #[test]
+    fn test_fibs() {
+        for &method in FibMethod::ALL {
+            test_fib(method.name(), method.function());
+        }
+        assert!(fib_doubling(usize::MAX).is_none());
+        assert!(fib_matrix(100).is_none());
+        assert!(fib_binet_exact(usize::MAX).is_none());
+        assert!(fib_recursive(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn test_small_indices() {
+        let methods = FibMethod::ALL.iter().map(|m| (m.name(), m.function()));
+        let naive: (&str, fn(usize) -> Option<u32>) = ("naive", fib_naive);
+        for (name, f) in methods.chain([naive]) {
+            assert_eq!(Some(0), f(0), "{}", name);
+            assert_eq!(Some(1), f(1), "{}", name);
+        }
+    }
+
+    /// A xorshift generator, so that the randomized tests don't
+    /// need an external crate.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+
+        #[cfg(feature = "std")]
+        fn shuffle<T>(&mut self, v: &mut [T]) {
+            for i in (1..v.len()).rev() {
+                v.swap(i, self.below(i + 1));
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_batch() {
+        let mut indices: Vec<usize> = (0..=47).chain([48, 100, usize::MAX]).collect();
+        Rng(0x9e37_79b9_7f4a_7c15).shuffle(&mut indices);
+        let naive: Vec<Option<u32>> = indices.iter().map(|&i| fib_array(i)).collect();
+        assert_eq!(naive, fib_batch(&indices));
+        assert!(fib_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_fib_random() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..1000 {
+            let n = rng.below(48);
+            let f = fib_array(n);
+            for &method in FibMethod::ALL {
+                assert_eq!(f, fib_with(method, n), "{:?}: F({})", method, n);
+            }
+            if n >= 2 {
+                assert_eq!(
+                    f,
+                    Some(fib_array(n - 1).unwrap() + fib_array(n - 2).unwrap())
+                );
+            }
+
+            // F(m + k) = F(m) F(k + 1) + F(m - 1) F(k)
+            let m = 1 + rng.below(46);
+            let k = rng.below(48 - m);
+            let fm = |i| u64::from(fib_array(i).unwrap());
+            let sum = fm(m) * fm(k + 1) + fm(m - 1) * fm(k);
+            assert_eq!(fm(m + k), sum, "F({} + {})", m, k);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fib_batch_parallel() {
+        let mut rng = Rng(0xdead_beef_cafe_f00d);
+        let indices: Vec<usize> = (0..10_000).map(|_| rng.below(60)).collect();
+        let sequential: Vec<Option<u32>> = indices.iter().map(|&i| fib_array(i)).collect();
+        assert_eq!(sequential, fib_batch_parallel(&indices));
+        assert!(fib_batch_parallel(&[]).is_empty());
     }
 }
@@ -0,0 +1,74 @@
+//! Print Fibonacci Numbers computed by the tour's
+//! implementations.
+//!
+//! ```text
+//! fib-tour [--method <name> | --all] <index>
+//! ```
+
+use std::process::exit;
+
+use fib_tour::*;
+
+fn usage() -> ! {
+    eprintln!("usage: fib-tour [--method <name> | --all] <index>");
+    let names: Vec<&str> = FibMethod::ALL.iter().map(|m| m.name()).collect();
+    eprintln!("methods: {}", names.join(", "));
+    exit(2);
+}
+
+fn main() {
+    let mut method = None;
+    let mut all = false;
+    let mut index = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--method" => method = Some(args.next().unwrap_or_else(|| usage())),
+            _ if index.is_none() => index = Some(arg),
+            _ => usage(),
+        }
+    }
+    let n = match index.map(|s| parse_index(&s)) {
+        Some(Ok(n)) => n,
+        Some(Err(e)) => {
+            eprintln!("fib-tour: {}", FibError::Parse(e));
+            exit(2);
+        }
+        None => usage(),
+    };
+
+    let methods: Vec<FibMethod> = match (all, method) {
+        (true, None) => FibMethod::ALL.to_vec(),
+        (false, None) => vec![FibMethod::Array],
+        (false, Some(name)) => match FibMethod::from_name(&name) {
+            Some(m) => vec![m],
+            None => {
+                eprintln!("fib-tour: unknown method {}", name);
+                usage();
+            }
+        },
+        (true, Some(_)) => usage(),
+    };
+
+    let mut overflowed = false;
+    for method in methods {
+        let result = match fib_with(method, n) {
+            Some(fib) => fib.to_string(),
+            None => {
+                overflowed = true;
+                FibError::Overflow { index: n }.to_string()
+            }
+        };
+        if all {
+            println!("{}: {}", method.name(), result);
+        } else if overflowed {
+            eprintln!("fib-tour: {}", result);
+        } else {
+            println!("{}", result);
+        }
+    }
+    if overflowed {
+        exit(1);
+    }
+}